@@ -1,8 +1,10 @@
 use clap::{Parser, Subcommand};
+use nu_ansi_term::Color;
 use regex::Regex;
 use serde_json::Value;
-use std::fs;
+use std::fs::{self, File};
 use std::collections::HashMap;
+use std::io::{BufRead, BufReader, IsTerminal, Write};
 
 #[derive(Parser)]
 #[command(name = "file-processor")]
@@ -18,6 +20,30 @@ enum Commands {
     Count {
         /// Input file path
         file: String,
+        /// Only report the byte count, read from file metadata without opening the content
+        #[arg(long)]
+        bytes_only: bool,
+        /// Classify lines as code, comment, or blank
+        #[arg(long)]
+        code_stats: bool,
+        /// Source language for --code-stats (default: inferred from the extension)
+        #[arg(long, requires = "code_stats")]
+        language: Option<String>,
+        /// Print counts in wc's column layout: lines words bytes filename
+        #[arg(long)]
+        wc: bool,
+        /// wc-style newline count only (combinable with -w, -m, -c)
+        #[arg(short = 'l', long)]
+        lines: bool,
+        /// wc-style word count
+        #[arg(short = 'w', long)]
+        words: bool,
+        /// wc-style character count
+        #[arg(short = 'm', long)]
+        chars: bool,
+        /// wc-style byte count
+        #[arg(short = 'c', long)]
+        bytes: bool,
     },
     /// Search for a pattern in a file
     Search {
@@ -28,17 +54,45 @@ enum Commands {
         /// Use regex for pattern matching
         #[arg(long)]
         regex: bool,
+        /// Stop reading the file after this many matching lines
+        #[arg(short, long, value_name = "N")]
+        max_count: Option<usize>,
+        /// Never highlight matches, even on a terminal (NO_COLOR is honored too)
+        #[arg(long)]
+        no_color: bool,
+        /// Print only the matched text, one match per line
+        #[arg(short, long)]
+        only_matching: bool,
+        /// With --only-matching, print this capture group instead of the whole match
+        #[arg(long, value_name = "N", requires = "only_matching")]
+        group: Option<usize>,
     },
     /// Replace text in a file
     Replace {
         /// Pattern to replace
-        pattern: String,
+        #[arg(required_unless_present = "rules")]
+        pattern: Option<String>,
         /// Replacement text
-        replacement: String,
+        #[arg(required_unless_present = "rules")]
+        replacement: Option<String>,
         /// Input file path
-        file: String,
+        #[arg(required_unless_present = "rules")]
+        file: Option<String>,
         /// Output file path
-        output: String,
+        #[arg(required_unless_present = "rules")]
+        output: Option<String>,
+        /// Apply a file of pattern<TAB>replacement lines, in order, to FILE and write OUTPUT
+        #[arg(long, num_args = 3, value_names = ["RULES", "FILE", "OUTPUT"], conflicts_with_all = ["pattern", "replacement"])]
+        rules: Option<Vec<String>>,
+        /// Treat patterns as regular expressions
+        #[arg(long)]
+        regex: bool,
+        /// Match patterns regardless of case
+        #[arg(short, long)]
+        ignore_case: bool,
+        /// Make each replacement follow the matched text's casing: UPPER, lower, or Title
+        #[arg(long, requires = "ignore_case")]
+        preserve_case: bool,
     },
     /// Convert CSV to JSON
     CsvToJson {
@@ -47,93 +101,525 @@ enum Commands {
         /// Output JSON file path
         output: String,
     },
+    /// Concatenate files in order into one output file
+    Concat {
+        /// Input file paths, in order
+        #[arg(required = true)]
+        inputs: Vec<String>,
+        /// Output file path
+        #[arg(short, long)]
+        output: String,
+        /// Line written between consecutive inputs
+        #[arg(long)]
+        separator: Option<String>,
+        /// Start each section with a "==> name <==" header line
+        #[arg(long)]
+        with_headers: bool,
+    },
+    /// Swap the rows and columns of a CSV file
+    Transpose {
+        /// Input CSV file path
+        input: String,
+        /// Output CSV file path
+        output: String,
+    },
+    /// Validate a JSON file and re-emit it pretty-printed or minified
+    Json {
+        /// Input JSON file path
+        input: String,
+        /// Output JSON file path
+        output: String,
+        /// Emit compact JSON with no whitespace
+        #[arg(long, conflicts_with = "pretty")]
+        minify: bool,
+        /// Emit indented JSON (the default)
+        #[arg(long)]
+        pretty: bool,
+        /// Recursively order object keys for stable diffs
+        #[arg(long)]
+        sort_keys: bool,
+    },
+    /// Expand tabs to spaces at each tab stop, or convert leading spaces back to tabs
+    Expand {
+        /// Input file path
+        input: String,
+        /// Output file path
+        output: String,
+        /// Columns between tab stops
+        #[arg(long, default_value_t = 4)]
+        tabs: usize,
+        /// Turn runs of leading spaces into tabs instead
+        #[arg(long)]
+        unexpand: bool,
+    },
+    /// Trim trailing whitespace and end the file with exactly one newline
+    Normalize {
+        /// Input file path
+        input: String,
+        /// Output file path
+        output: String,
+        /// Collapse runs of blank lines into one
+        #[arg(long)]
+        squeeze_blank: bool,
+        /// Convert line endings to LF
+        #[arg(long, conflicts_with = "crlf")]
+        lf: bool,
+        /// Convert line endings to CRLF
+        #[arg(long)]
+        crlf: bool,
+    },
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Count { file } => {
-            count_file_stats(&file)?;
+        Commands::Count { file, bytes_only, code_stats, language, wc, lines, words, chars, bytes } => {
+            if bytes_only {
+                println!("Bytes: {}", fs::metadata(&file)?.len());
+            } else if code_stats {
+                count_code_lines(&file, language.as_deref())?;
+            } else if wc || lines || words || chars || bytes {
+                let stats = count_stats(BufReader::new(File::open(&file)?))?;
+                println!("{}", format_wc(&stats, WcColumns::from_flags(lines, words, chars, bytes), &file));
+            } else {
+                count_file_stats(&file)?;
+            }
         }
-        Commands::Search { pattern, file, regex } => {
-            search_in_file(&pattern, &file, regex)?;
+        Commands::Search { pattern, file, regex, max_count, no_color, only_matching, group } => {
+            let options = SearchOptions {
+                max_count,
+                color: use_color(no_color),
+                only_matching,
+                group: group.unwrap_or(0),
+            };
+            search_in_file(&pattern, &file, regex, options)?;
         }
-        Commands::Replace { pattern, replacement, file, output } => {
-            replace_in_file(&pattern, &replacement, &file, &output)?;
+        Commands::Replace { pattern, replacement, file, output, rules, regex, ignore_case, preserve_case } => {
+            let options = ReplaceOptions { regex, ignore_case, preserve_case };
+            match (rules.as_deref(), pattern, replacement, file, output) {
+                (Some([rules_path, file, output]), ..) => {
+                    let rules = parse_rules(&fs::read_to_string(rules_path)?)?;
+                    replace_in_file(&rules, options, file, output)?;
+                }
+                (None, Some(pattern), Some(replacement), Some(file), Some(output)) => {
+                    replace_in_file(&[(pattern, replacement)], options, &file, &output)?;
+                }
+                _ => unreachable!("clap requires all four positionals or --rules RULES FILE OUTPUT"),
+            }
         }
         Commands::CsvToJson { input, output } => {
             convert_csv_to_json(&input, &output)?;
         }
+        Commands::Concat { inputs, output, separator, with_headers } => {
+            concat_files(&inputs, &output, separator.as_deref(), with_headers)?;
+        }
+        Commands::Transpose { input, output } => {
+            transpose_file(&input, &output)?;
+        }
+        Commands::Json { input, output, minify, pretty: _, sort_keys } => {
+            reformat_json_file(&input, &output, minify, sort_keys)?;
+        }
+        Commands::Expand { input, output, tabs, unexpand } => {
+            expand_file(&input, &output, tabs.max(1), unexpand)?;
+        }
+        Commands::Normalize { input, output, squeeze_blank, lf, crlf } => {
+            let line_ending = match (lf, crlf) {
+                (true, _) => Some("\n"),
+                (_, true) => Some("\r\n"),
+                _ => None,
+            };
+            normalize_file(&input, &output, squeeze_blank, line_ending)?;
+        }
     }
 
     Ok(())
 }
 
+#[derive(Debug, Default, PartialEq)]
+struct FileStats {
+    lines: usize,
+    /// Newline characters, which is what wc reports as lines
+    newlines: usize,
+    words: usize,
+    chars: usize,
+    bytes: usize,
+}
+
 fn count_file_stats(file_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let content = fs::read_to_string(file_path)?;
-    
-    let lines = content.lines().count();
-    let words = content.split_whitespace().count();
-    let chars = content.chars().count();
-    let bytes = content.len();
+    let stats = count_stats(BufReader::new(File::open(file_path)?))?;
     
     println!("File: {}", file_path);
-    println!("Lines: {}", lines);
-    println!("Words: {}", words);
-    println!("Characters: {}", chars);
-    println!("Bytes: {}", bytes);
+    println!("Lines: {}", stats.lines);
+    println!("Words: {}", stats.words);
+    println!("Characters: {}", stats.chars);
+    println!("Bytes: {}", stats.bytes);
     
     Ok(())
 }
 
-fn search_in_file(pattern: &str, file_path: &str, use_regex: bool) -> Result<(), Box<dyn std::error::Error>> {
-    let content = fs::read_to_string(file_path)?;
+/// Counts one line at a time, so memory use stays flat however large the file is
+fn count_stats<R: BufRead>(mut reader: R) -> std::io::Result<FileStats> {
+    let mut stats = FileStats::default();
+    let mut line = Vec::new();
+    
+    while reader.read_until(b'\n', &mut line)? > 0 {
+        // Lossy decoding keeps going past invalid UTF-8 instead of failing the whole count
+        let text = String::from_utf8_lossy(&line);
+        stats.lines += 1;
+        stats.newlines += usize::from(line.ends_with(b"\n"));
+        stats.words += text.split_whitespace().count();
+        stats.chars += text.chars().count();
+        stats.bytes += line.len();
+        line.clear();
+    }
+    
+    Ok(stats)
+}
+
+/// Which counts wc-style output shows; the fields are in wc's column order
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct WcColumns {
+    lines: bool,
+    words: bool,
+    chars: bool,
+    bytes: bool,
+}
+
+impl WcColumns {
+    /// With none of -l/-w/-m/-c, shows wc's default of lines, words, and bytes
+    fn from_flags(lines: bool, words: bool, chars: bool, bytes: bool) -> Self {
+        if lines || words || chars || bytes {
+            Self { lines, words, chars, bytes }
+        } else {
+            Self { lines: true, words: true, chars: false, bytes: true }
+        }
+    }
+}
+
+/// Formats counts the way GNU wc does for one file: a lone count is unpadded, otherwise
+/// every column is right-aligned to the width of the byte count
+fn format_wc(stats: &FileStats, columns: WcColumns, file_path: &str) -> String {
+    let counts: Vec<usize> = [
+        (columns.lines, stats.newlines),
+        (columns.words, stats.words),
+        (columns.chars, stats.chars),
+        (columns.bytes, stats.bytes),
+    ]
+    .into_iter()
+    .filter_map(|(shown, count)| shown.then_some(count))
+    .collect();
+    
+    let width = if counts.len() == 1 { 1 } else { stats.bytes.to_string().len() };
+    let mut line: String = counts.iter().map(|count| format!("{:>width$} ", count)).collect();
+    line.push_str(file_path);
+    line
+}
+
+/// How a language spells its comments
+#[derive(Debug, Clone, Copy)]
+struct CommentSyntax {
+    line: &'static str,
+    block: Option<(&'static str, &'static str)>,
+}
+
+fn comment_syntax(language: &str) -> Option<CommentSyntax> {
+    let c_like = CommentSyntax { line: "//", block: Some(("/*", "*/")) };
+    let hash = CommentSyntax { line: "#", block: None };
+    
+    match language.to_lowercase().as_str() {
+        "rust" | "c" | "cpp" | "java" | "javascript" | "typescript" | "go" => Some(c_like),
+        "python" | "shell" | "ruby" | "toml" | "yaml" => Some(hash),
+        _ => None,
+    }
+}
+
+fn language_from_extension(file_path: &str) -> Option<&'static str> {
+    let extension = std::path::Path::new(file_path).extension()?.to_str()?;
     
-    if use_regex {
-        let re = Regex::new(pattern)?;
-        let mut found = false;
+    Some(match extension {
+        "rs" => "rust",
+        "c" | "h" => "c",
+        "cpp" | "cc" | "hpp" => "cpp",
+        "java" => "java",
+        "js" => "javascript",
+        "ts" => "typescript",
+        "go" => "go",
+        "py" => "python",
+        "sh" | "bash" => "shell",
+        "rb" => "ruby",
+        "toml" => "toml",
+        "yml" | "yaml" => "yaml",
+        _ => return None,
+    })
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct CodeStats {
+    code: usize,
+    comment: usize,
+    blank: usize,
+}
+
+/// Classifies lines one at a time, carrying "inside a block comment" across lines
+struct LineClassifier {
+    syntax: CommentSyntax,
+    in_block: bool,
+    stats: CodeStats,
+}
+
+impl LineClassifier {
+    fn new(syntax: CommentSyntax) -> Self {
+        Self { syntax, in_block: false, stats: CodeStats::default() }
+    }
+    
+    /// A line with any code on it counts as code, even if it also has a comment
+    fn feed(&mut self, line: &str) {
+        if line.trim().is_empty() {
+            self.stats.blank += 1;
+            return;
+        }
         
-        for (line_num, line) in content.lines().enumerate() {
-            if re.is_match(line) {
-                println!("{}:{}: {}", file_path, line_num + 1, line);
-                found = true;
+        let mut has_code = false;
+        let mut rest = line;
+        
+        loop {
+            if self.in_block {
+                let (_, end) = self.syntax.block.unwrap();
+                match rest.find(end) {
+                    Some(pos) => {
+                        rest = &rest[pos + end.len()..];
+                        self.in_block = false;
+                    }
+                    None => break,
+                }
+            }
+            
+            rest = rest.trim_start();
+            if rest.is_empty() || rest.starts_with(self.syntax.line) {
+                break;
+            }
+            if let Some((start, _)) = self.syntax.block.filter(|(start, _)| rest.starts_with(start)) {
+                rest = &rest[start.len()..];
+                self.in_block = true;
+                continue;
+            }
+            
+            // Code runs until the next comment marker, if any
+            has_code = true;
+            let next_line = rest.find(self.syntax.line);
+            let next_block = self.syntax.block.and_then(|(start, _)| rest.find(start));
+            match (next_line, next_block) {
+                (_, Some(block)) if next_line.is_none_or(|line| block < line) => rest = &rest[block..],
+                _ => break,
             }
         }
         
-        if !found {
+        if has_code {
+            self.stats.code += 1;
+        } else {
+            self.stats.comment += 1;
+        }
+    }
+}
+
+fn count_code_lines(file_path: &str, language: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let language = language
+        .or_else(|| language_from_extension(file_path))
+        .ok_or("can't infer the language from the extension; pass --language")?;
+    let syntax = comment_syntax(language).ok_or_else(|| format!("unsupported language: {}", language))?;
+    
+    let mut classifier = LineClassifier::new(syntax);
+    for line in BufReader::new(File::open(file_path)?).lines() {
+        classifier.feed(&line?);
+    }
+    let stats = classifier.stats;
+    
+    println!("File: {} ({})", file_path, language);
+    println!("Code: {}", stats.code);
+    println!("Comments: {}", stats.comment);
+    println!("Blank: {}", stats.blank);
+    println!("Total: {}", stats.code + stats.comment + stats.blank);
+    
+    Ok(())
+}
+
+/// How Search prints what it finds
+#[derive(Debug, Default, Clone, Copy)]
+struct SearchOptions {
+    max_count: Option<usize>,
+    color: bool,
+    only_matching: bool,
+    /// Capture group printed by --only-matching; 0 is the whole match
+    group: usize,
+}
+
+/// Highlights only when stdout is a terminal and neither --no-color nor a non-empty NO_COLOR asks otherwise
+fn use_color(no_color: bool) -> bool {
+    let no_color_env = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+    !no_color && !no_color_env && std::io::stdout().is_terminal()
+}
+
+fn search_in_file(pattern: &str, file_path: &str, use_regex: bool, options: SearchOptions) -> Result<(), Box<dyn std::error::Error>> {
+    // Literal patterns are escaped so both modes share one matcher
+    let re = Regex::new(&if use_regex { pattern.to_string() } else { regex::escape(pattern) })?;
+    if options.group >= re.captures_len() {
+        return Err(format!("--group {}: the pattern has only {} capture group(s)", options.group, re.captures_len() - 1).into());
+    }
+    let reader = BufReader::new(File::open(file_path)?);
+    
+    let found = search_lines(reader, file_path, &re, options, &mut std::io::stdout().lock())?;
+    
+    if found == 0 {
+        if use_regex {
             println!("No matches found for regex pattern: {}", pattern);
+        } else {
+            println!("No matches found for pattern: {}", pattern);
+        }
+    }
+    
+    Ok(())
+}
+
+/// Prints each matching line as `path:line: text` and returns how many matched. With a
+/// max count, reading stops at that many matches instead of scanning the rest of the file
+fn search_lines<R: BufRead, W: Write>(reader: R, file_path: &str, re: &Regex, options: SearchOptions, out: &mut W) -> std::io::Result<usize> {
+    let mut found = 0;
+    
+    for (line_num, line) in reader.lines().enumerate() {
+        if options.max_count.is_some_and(|max| found >= max) {
+            break;
         }
-    } else {
-        let mut found = false;
         
-        for (line_num, line) in content.lines().enumerate() {
-            if line.contains(pattern) {
-                println!("{}:{}: {}", file_path, line_num + 1, line);
-                found = true;
-            }
+        let line = line?;
+        if !re.is_match(&line) {
+            continue;
         }
+        found += 1;
         
-        if !found {
-            println!("No matches found for pattern: {}", pattern);
+        if options.only_matching {
+            // A group that didn't take part in a match prints nothing for it
+            for text in re.captures_iter(&line).filter_map(|caps| caps.get(options.group)) {
+                let text = text.as_str();
+                if options.color {
+                    writeln!(out, "{}:{}: {}", file_path, line_num + 1, Color::Red.bold().paint(text))?;
+                } else {
+                    writeln!(out, "{}:{}: {}", file_path, line_num + 1, text)?;
+                }
+            }
+        } else {
+            let line = if options.color { highlight_matches(&line, re) } else { line };
+            writeln!(out, "{}:{}: {}", file_path, line_num + 1, line)?;
         }
     }
     
-    Ok(())
+    Ok(found)
 }
 
-fn replace_in_file(pattern: &str, replacement: &str, input_path: &str, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+/// Wraps every match on the line in bold red ANSI escapes
+fn highlight_matches(line: &str, re: &Regex) -> String {
+    let style = Color::Red.bold();
+    re.replace_all(line, |caps: &regex::Captures| style.paint(&caps[0]).to_string()).into_owned()
+}
+
+/// How Replace interprets its rules
+#[derive(Debug, Default, Clone, Copy)]
+struct ReplaceOptions {
+    regex: bool,
+    ignore_case: bool,
+    preserve_case: bool,
+}
+
+fn replace_in_file(rules: &[(String, String)], options: ReplaceOptions, input_path: &str, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
     let content = fs::read_to_string(input_path)?;
-    let modified_content = content.replace(pattern, replacement);
+    let modified_content = apply_rules(&content, rules, options)?;
     
     fs::write(output_path, modified_content)?;
     
-    println!("Replaced '{}' with '{}' in {}", pattern, replacement, input_path);
+    for (pattern, replacement) in rules {
+        println!("Replaced '{}' with '{}' in {}", pattern, replacement, input_path);
+    }
     println!("Output written to: {}", output_path);
     
     Ok(())
 }
 
+/// Parses a rules file: one `pattern<TAB>replacement` pair per line, blank lines ignored
+fn parse_rules(content: &str) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+    let mut rules = Vec::new();
+    
+    for (line_num, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        
+        match line.split_once('\t') {
+            Some((pattern, replacement)) if !pattern.is_empty() => {
+                rules.push((pattern.to_string(), replacement.to_string()));
+            }
+            _ => {
+                return Err(format!("rules line {}: expected pattern<TAB>replacement, got '{}'", line_num + 1, line).into());
+            }
+        }
+    }
+    
+    Ok(rules)
+}
+
+/// Applies each rule in order, so later rules see the output of earlier ones
+fn apply_rules(content: &str, rules: &[(String, String)], options: ReplaceOptions) -> Result<String, Box<dyn std::error::Error>> {
+    let mut result = content.to_string();
+    
+    for (pattern, replacement) in rules {
+        if !options.regex && !options.ignore_case {
+            result = result.replace(pattern.as_str(), replacement);
+            continue;
+        }
+        
+        // Case-insensitive literal patterns still go through the regex engine, escaped
+        let pattern = if options.regex { pattern.clone() } else { regex::escape(pattern) };
+        let re = regex::RegexBuilder::new(&pattern).case_insensitive(options.ignore_case).build()?;
+        
+        result = re.replace_all(&result, |caps: &regex::Captures| {
+            let mut expanded = String::new();
+            if options.regex {
+                caps.expand(replacement, &mut expanded);
+            } else {
+                expanded.push_str(replacement);
+            }
+            
+            if options.preserve_case {
+                match_case(&caps[0], &expanded)
+            } else {
+                expanded
+            }
+        }).into_owned();
+    }
+    
+    Ok(result)
+}
+
+/// Recases `replacement` after `matched` when that is ALL CAPS, all lowercase, or Title
+/// case; mixed casing such as "cOlOr" leaves the replacement as written
+fn match_case(matched: &str, replacement: &str) -> String {
+    let letters: Vec<char> = matched.chars().filter(|c| c.is_alphabetic()).collect();
+    let Some((first, rest)) = letters.split_first() else {
+        return replacement.to_string();
+    };
+    
+    if letters.iter().all(|c| c.is_uppercase()) && letters.len() > 1 {
+        replacement.to_uppercase()
+    } else if letters.iter().all(|c| c.is_lowercase()) {
+        replacement.to_lowercase()
+    } else if first.is_uppercase() && rest.iter().all(|c| c.is_lowercase()) {
+        let lower = replacement.to_lowercase();
+        let mut chars = lower.chars();
+        chars.next().map_or_else(String::new, |c| c.to_uppercase().chain(chars).collect())
+    } else {
+        replacement.to_string()
+    }
+}
+
 fn convert_csv_to_json(input_path: &str, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
     let content = fs::read_to_string(input_path)?;
     let lines: Vec<&str> = content.lines().collect();
@@ -171,3 +657,459 @@ fn convert_csv_to_json(input_path: &str, output_path: &str) -> Result<(), Box<dy
     
     Ok(())
 }
+
+fn concat_files(inputs: &[String], output_path: &str, separator: Option<&str>, with_headers: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut writer = std::io::BufWriter::new(File::create(output_path)?);
+    let bytes = concat_into(inputs, separator, with_headers, &mut writer)?;
+    writer.flush()?;
+    
+    println!("Concatenated {} files ({} bytes)", inputs.len(), bytes);
+    println!("Output written to: {}", output_path);
+    
+    Ok(())
+}
+
+/// Streams each input into `out` a buffer at a time, so file size doesn't matter, and returns
+/// the bytes copied. An input missing its final newline gets one before the next separator or header
+fn concat_into<W: Write>(inputs: &[String], separator: Option<&str>, with_headers: bool, out: &mut W) -> std::io::Result<u64> {
+    let mut copied = 0;
+    let mut needs_newline = false;
+    
+    for (index, path) in inputs.iter().enumerate() {
+        let mut reader = BufReader::new(File::open(path)?);
+        
+        if needs_newline && (separator.is_some() || with_headers) {
+            out.write_all(b"\n")?;
+        }
+        if let Some(separator) = separator.filter(|_| index > 0) {
+            writeln!(out, "{}", separator)?;
+        }
+        if with_headers {
+            writeln!(out, "==> {} <==", path)?;
+        }
+        
+        loop {
+            let buffer = reader.fill_buf()?;
+            let Some(&last) = buffer.last() else { break };
+            out.write_all(buffer)?;
+            needs_newline = last != b'\n';
+            
+            let len = buffer.len();
+            reader.consume(len);
+            copied += len as u64;
+        }
+    }
+    
+    Ok(copied)
+}
+
+fn transpose_file(input_path: &str, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let (grid, rectangular) = transpose_csv(File::open(input_path)?)?;
+    
+    let mut writer = csv::Writer::from_path(output_path)?;
+    for row in &grid {
+        writer.write_record(row)?;
+    }
+    writer.flush()?;
+    
+    if !rectangular {
+        println!("Warning: {} has rows of differing lengths; short rows were padded with empty cells", input_path);
+    }
+    println!("Transposed {} into {} rows of {} columns", input_path, grid.len(), grid.first().map_or(0, Vec::len));
+    println!("Output written to: {}", output_path);
+    
+    Ok(())
+}
+
+/// Reads a CSV (quoted fields and all) into a grid and returns it transposed, along with
+/// whether every input row had the same length. Short rows are padded with empty cells
+fn transpose_csv<R: std::io::Read>(input: R) -> Result<(Vec<Vec<String>>, bool), csv::Error> {
+    let mut reader = csv::ReaderBuilder::new().has_headers(false).flexible(true).from_reader(input);
+    let rows = reader
+        .records()
+        .map(|record| record.map(|r| r.iter().map(String::from).collect::<Vec<_>>()))
+        .collect::<Result<Vec<_>, _>>()?;
+    
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let rectangular = rows.iter().all(|row| row.len() == width);
+    
+    let transposed = (0..width)
+        .map(|column| rows.iter().map(|row| row.get(column).cloned().unwrap_or_default()).collect())
+        .collect();
+    
+    Ok((transposed, rectangular))
+}
+
+fn reformat_json_file(input_path: &str, output_path: &str, minify: bool, sort_keys: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(input_path)?;
+    let json = reformat_json(&content, minify, sort_keys)
+        .map_err(|e| format!("{}: {}", input_path, e))?;
+    
+    fs::write(output_path, json)?;
+    
+    println!("Reformatted {} as {} JSON", input_path, if minify { "minified" } else { "pretty" });
+    println!("Output written to: {}", output_path);
+    
+    Ok(())
+}
+
+/// Parses a JSON document and re-serializes it; parse failures report where they happened
+fn reformat_json(content: &str, minify: bool, sort_keys: bool) -> Result<String, String> {
+    let mut value: Value = serde_json::from_str(content)
+        .map_err(|e| format!("invalid JSON at line {}, column {}: {}", e.line(), e.column(), e))?;
+    
+    if sort_keys {
+        value = sort_json_keys(value);
+    }
+    
+    let json = if minify {
+        serde_json::to_string(&value)
+    } else {
+        serde_json::to_string_pretty(&value)
+    };
+    json.map_err(|e| e.to_string())
+}
+
+/// Rebuilds every object with its keys in order. serde_json only keeps insertion order
+/// with its `preserve_order` feature, so sorting explicitly is stable either way
+fn sort_json_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            Value::Object(entries.into_iter().map(|(key, value)| (key, sort_json_keys(value))).collect())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_json_keys).collect()),
+        other => other,
+    }
+}
+
+fn expand_file(input_path: &str, output_path: &str, tab_width: usize, unexpand: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(input_path)?;
+    
+    // split_inclusive keeps each line's ending, so the output preserves it exactly
+    let converted: String = content
+        .split_inclusive('\n')
+        .map(|line| if unexpand { unexpand_line(line, tab_width) } else { expand_line(line, tab_width) })
+        .collect();
+    
+    fs::write(output_path, converted)?;
+    
+    println!("{} {} (tab stops every {} columns)",
+             if unexpand { "Unexpanded" } else { "Expanded" }, input_path, tab_width);
+    println!("Output written to: {}", output_path);
+    
+    Ok(())
+}
+
+/// Replaces each tab with however many spaces reach the next tab stop
+fn expand_line(line: &str, tab_width: usize) -> String {
+    let mut expanded = String::with_capacity(line.len());
+    let mut column = 0;
+    
+    for c in line.chars() {
+        if c == '\t' {
+            let spaces = tab_width - column % tab_width;
+            expanded.push_str(&" ".repeat(spaces));
+            column += spaces;
+        } else {
+            expanded.push(c);
+            column += 1;
+        }
+    }
+    
+    expanded
+}
+
+/// Rewrites the leading indentation as tabs (plus any leftover spaces); the rest is untouched
+fn unexpand_line(line: &str, tab_width: usize) -> String {
+    let indent_len = line.len() - line.trim_start_matches([' ', '\t']).len();
+    let (indent, rest) = line.split_at(indent_len);
+    let width = expand_line(indent, tab_width).len();
+    
+    format!("{}{}{}", "\t".repeat(width / tab_width), " ".repeat(width % tab_width), rest)
+}
+
+fn normalize_file(input_path: &str, output_path: &str, squeeze_blank: bool, line_ending: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    let content = fs::read_to_string(input_path)?;
+    let normalized = normalize_text(&content, squeeze_blank, line_ending);
+    
+    fs::write(output_path, &normalized)?;
+    
+    println!("Normalized {} ({} -> {} bytes)", input_path, content.len(), normalized.len());
+    println!("Output written to: {}", output_path);
+    
+    Ok(())
+}
+
+/// Trims each line's trailing whitespace, optionally squeezes blank runs, and ends with one
+/// newline. Without an explicit ending, CRLF input stays CRLF and anything else becomes LF
+fn normalize_text(content: &str, squeeze_blank: bool, line_ending: Option<&str>) -> String {
+    let ending = line_ending.unwrap_or(if content.contains("\r\n") { "\r\n" } else { "\n" });
+    let mut lines: Vec<&str> = Vec::new();
+    
+    // trim_end also strips the \r of a CRLF ending
+    for line in content.lines().map(str::trim_end) {
+        if squeeze_blank && line.is_empty() && lines.last() == Some(&"") {
+            continue;
+        }
+        lines.push(line);
+    }
+    
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    if lines.is_empty() {
+        return String::new();
+    }
+    
+    let mut normalized = lines.join(ending);
+    normalized.push_str(ending);
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_rules_file_applies_in_order() {
+        let rules = parse_rules("cat\tdog\n\ndog\twolf\n(\\d+)\t<$1>\n").unwrap();
+        assert_eq!(rules.len(), 3);
+        
+        // Literal mode: the third rule's pattern is taken verbatim and matches nothing
+        let literal = apply_rules("cat 42", &rules, ReplaceOptions::default()).unwrap();
+        assert_eq!(literal, "wolf 42");
+        
+        // Regex mode: cat -> dog -> wolf, and the capture group wraps the number
+        let regex = apply_rules("cat 42", &rules, ReplaceOptions { regex: true, ..Default::default() }).unwrap();
+        assert_eq!(regex, "wolf <42>");
+    }
+    
+    #[test]
+    fn test_replace_takes_named_positionals_or_rules() {
+        let cli = Cli::try_parse_from(["file-processor", "replace", "cat", "dog", "in.txt", "out.txt"]).unwrap();
+        let Commands::Replace { pattern, output, rules, .. } = cli.command else { panic!("expected Replace") };
+        assert_eq!((pattern.as_deref(), output.as_deref(), rules), (Some("cat"), Some("out.txt"), None));
+        
+        let cli = Cli::try_parse_from(["file-processor", "replace", "--rules", "rules.tsv", "in.txt", "out.txt"]).unwrap();
+        let Commands::Replace { pattern, rules, .. } = cli.command else { panic!("expected Replace") };
+        assert_eq!(pattern, None);
+        assert_eq!(rules.unwrap(), ["rules.tsv", "in.txt", "out.txt"]);
+        
+        assert!(Cli::try_parse_from(["file-processor", "replace", "cat", "in.txt", "out.txt"]).is_err());
+        assert!(Cli::try_parse_from(["file-processor", "replace", "--rules", "rules.tsv", "in.txt", "out.txt", "cat"]).is_err());
+    }
+    
+    #[test]
+    fn test_json_minify_then_pretty_round_trip() {
+        let original = r#"{
+          "name": "demo",
+          "tags": ["a", "b"],
+          "nested": { "z": 1, "a": [true, null, 2.5] }
+        }"#;
+        
+        let minified = reformat_json(original, true, false).unwrap();
+        assert!(!minified.contains(char::is_whitespace));
+        
+        let pretty = reformat_json(&minified, false, false).unwrap();
+        assert!(pretty.contains("\n  \"tags\": [\n"));
+        assert_eq!(reformat_json(&pretty, true, false).unwrap(), minified);
+        
+        let parsed = |json: &str| serde_json::from_str::<Value>(json).unwrap();
+        assert_eq!(parsed(&pretty), parsed(original));
+        
+        let sorted = reformat_json(original, true, true).unwrap();
+        assert_eq!(sorted, r#"{"name":"demo","nested":{"a":[true,null,2.5],"z":1},"tags":["a","b"]}"#);
+    }
+    
+    #[test]
+    fn test_invalid_json_reports_position() {
+        let error = reformat_json("{\n  \"a\": 1,\n  oops\n}", false, false).unwrap_err();
+        assert!(error.contains("line 3, column 3"), "{}", error);
+    }
+    
+    #[test]
+    fn test_expand_aligns_to_tab_stops() {
+        // Each tab jumps to the next multiple of 4, wherever it starts
+        assert_eq!(expand_line("\tid\tname\n", 4), "    id  name\n");
+        assert_eq!(expand_line("abc\tx\tlonger\ty", 4), "abc x   longer  y");
+        assert_eq!(expand_line("a\tb", 8), "a       b");
+        
+        assert_eq!(unexpand_line("      indented\tkeep\n", 4), "\t  indented\tkeep\n");
+        assert_eq!(unexpand_line(" \t  x", 4), "\t  x");
+        assert_eq!(unexpand_line(&expand_line("\t\tx", 4), 4), "\t\tx");
+    }
+    
+    #[test]
+    fn test_normalize_trims_and_fixes_endings() {
+        let messy = "fn main() {  \r\n    todo!();\t\r\n\r\n\r\n\r\n}\r\n\r\n\r\n";
+        
+        assert_eq!(normalize_text(messy, true, Some("\n")), "fn main() {\n    todo!();\n\n}\n");
+        assert_eq!(normalize_text(messy, false, Some("\n")), "fn main() {\n    todo!();\n\n\n\n}\n");
+        
+        // Without --lf/--crlf the CRLF endings survive, minus the trailing whitespace
+        assert_eq!(normalize_text(messy, true, None), "fn main() {\r\n    todo!();\r\n\r\n}\r\n");
+        assert_eq!(normalize_text("no newline", false, Some("\r\n")), "no newline\r\n");
+    }
+    
+    #[test]
+    fn test_bytes_only_matches_full_count() {
+        let path = std::env::temp_dir().join(format!("file-processor-count-{}.txt", std::process::id()));
+        fs::write(&path, "héllo wörld\nsecond line\n\nno trailing newline").unwrap();
+        
+        let stats = count_stats(BufReader::new(File::open(&path).unwrap())).unwrap();
+        let metadata_bytes = fs::metadata(&path).unwrap().len() as usize;
+        fs::remove_file(&path).unwrap();
+        
+        assert_eq!(stats.bytes, metadata_bytes);
+        assert_eq!(stats, FileStats { lines: 4, newlines: 3, words: 7, chars: 44, bytes: 46 });
+    }
+    
+    #[test]
+    fn test_code_stats_handle_block_comments() {
+        let snippet = [
+            "/* A block comment",
+            "",
+            "   spanning lines */",
+            "fn main() { // trailing comment",
+            "    let x = 1; /* inline */ let y = 2;",
+            "    /* just a comment */",
+            "",
+            "    // line comment",
+            "    /* opens here */ println!(\"{}\", x + y);",
+            "}",
+        ];
+        
+        let mut classifier = LineClassifier::new(comment_syntax("rust").unwrap());
+        for line in snippet {
+            classifier.feed(line);
+        }
+        assert_eq!(classifier.stats, CodeStats { code: 4, comment: 4, blank: 2 });
+        
+        assert_eq!(language_from_extension("src/main.rs"), Some("rust"));
+        assert_eq!(language_from_extension("README"), None);
+    }
+    
+    #[test]
+    fn test_transpose_swaps_rows_and_columns() {
+        let (grid, rectangular) = transpose_csv("name,age,city\nAda,36,\"London, UK\"\n".as_bytes()).unwrap();
+        
+        assert!(rectangular);
+        assert_eq!(grid, vec![
+            vec!["name", "Ada"],
+            vec!["age", "36"],
+            vec!["city", "London, UK"],
+        ]);
+        
+        let (ragged, rectangular) = transpose_csv("a,b,c\nd\n".as_bytes()).unwrap();
+        assert!(!rectangular);
+        assert_eq!(ragged[2], vec!["c", ""]);
+    }
+    
+    #[test]
+    fn test_preserve_case_follows_each_match() {
+        let rules = vec![("color".to_string(), "colour".to_string())];
+        let text = "color COLOR Color cOLoR";
+        
+        let plain = ReplaceOptions { ignore_case: true, ..Default::default() };
+        assert_eq!(apply_rules(text, &rules, plain).unwrap(), "colour colour colour colour");
+        
+        let preserve = ReplaceOptions { preserve_case: true, ..plain };
+        assert_eq!(apply_rules(text, &rules, preserve).unwrap(), "colour COLOUR Colour colour");
+        
+        // Regex replacements are expanded first, then recased
+        let rules = vec![("(gr[ae]y)".to_string(), "${1}ish".to_string())];
+        let regex = ReplaceOptions { regex: true, ..preserve };
+        assert_eq!(apply_rules("GREY Gray", &rules, regex).unwrap(), "GREYISH Grayish");
+    }
+    
+    #[test]
+    fn test_max_count_stops_after_n_matches() {
+        let text = "match one\nskip\nmatch two\nmatch three\nmatch four\n";
+        let re = Regex::new("match").unwrap();
+        
+        let mut out = Vec::new();
+        let found = search_lines(text.as_bytes(), "log.txt", &re, SearchOptions { max_count: Some(2), ..Default::default() }, &mut out).unwrap();
+        assert_eq!(found, 2);
+        assert_eq!(String::from_utf8(out).unwrap(), "log.txt:1: match one\nlog.txt:3: match two\n");
+        
+        let found = search_lines(text.as_bytes(), "log.txt", &re, SearchOptions::default(), &mut std::io::sink()).unwrap();
+        assert_eq!(found, 4);
+    }
+    
+    #[test]
+    fn test_color_highlights_every_match() {
+        let re = Regex::new(r"\d+").unwrap();
+        let mut out = Vec::new();
+        search_lines("ids 12 and 345\n".as_bytes(), "ids.txt", &re, SearchOptions { color: true, ..Default::default() }, &mut out).unwrap();
+        
+        let printed = String::from_utf8(out).unwrap();
+        assert_eq!(printed, "ids.txt:1: ids \x1b[1;31m12\x1b[0m and \x1b[1;31m345\x1b[0m\n");
+    }
+    
+    #[test]
+    fn test_only_matching_extracts_tokens() {
+        let text = "GET /index 200 12ms\nno numbers here\nPOST /login 401 7ms\n";
+        let re = Regex::new(r"(\d+)ms").unwrap();
+        let search = |options| {
+            let mut out = Vec::new();
+            search_lines(text.as_bytes(), "access.log", &re, options, &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        
+        let whole = SearchOptions { only_matching: true, ..Default::default() };
+        assert_eq!(search(whole), "access.log:1: 12ms\naccess.log:3: 7ms\n");
+        assert_eq!(search(SearchOptions { group: 1, ..whole }), "access.log:1: 12\naccess.log:3: 7\n");
+    }
+    
+    #[test]
+    fn test_wc_output_matches_wc_columns() {
+        let text = "héllo wörld\nsecond line\n\nno trailing newline";
+        let stats = count_stats(text.as_bytes()).unwrap();
+        let columns = |l, w, m, c| WcColumns::from_flags(l, w, m, c);
+        
+        // Captured from GNU coreutils `wc -lwc k.txt`, `wc -l k.txt`, and `wc -lw k.txt`
+        assert_eq!(format_wc(&stats, columns(false, false, false, false), "k.txt"), " 3  7 46 k.txt");
+        assert_eq!(format_wc(&stats, columns(true, false, false, false), "k.txt"), "3 k.txt");
+        assert_eq!(format_wc(&stats, columns(true, true, false, false), "k.txt"), " 3  7 k.txt");
+        
+        // -m counts characters, which differ from bytes once the text isn't ASCII
+        assert_eq!(format_wc(&stats, columns(false, false, true, true), "k.txt"), "44 46 k.txt");
+    }
+    
+    #[test]
+    fn test_concat_joins_files_with_separators() {
+        let dir = std::env::temp_dir().join(format!("file-processor-concat-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let inputs: Vec<String> = [("a.txt", "one\n"), ("b.txt", "two\nlines\n"), ("c.txt", "no newline")]
+            .iter()
+            .map(|(name, content)| {
+                let path = dir.join(name);
+                fs::write(&path, content).unwrap();
+                path.to_string_lossy().into_owned()
+            })
+            .collect();
+        
+        let concat = |separator, with_headers| {
+            let mut out = Vec::new();
+            concat_into(&inputs, separator, with_headers, &mut out).unwrap();
+            String::from_utf8(out).unwrap()
+        };
+        let plain = concat(None, false);
+        let separated = concat(Some("---"), false);
+        let headed = concat(None, true);
+        fs::remove_dir_all(&dir).unwrap();
+        
+        assert_eq!(plain, "one\ntwo\nlines\nno newline");
+        assert_eq!(separated, "one\n---\ntwo\nlines\n---\nno newline");
+        assert_eq!(headed.lines().filter(|line| line.starts_with("==> ")).count(), 3);
+        assert!(headed.ends_with("c.txt <==\nno newline"));
+    }
+    
+    #[test]
+    fn test_malformed_rules_line_is_an_error() {
+        let error = parse_rules("cat\tdog\nno tab here\n").unwrap_err();
+        assert!(error.to_string().contains("rules line 2"), "{}", error);
+    }
+}
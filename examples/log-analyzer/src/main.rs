@@ -1,10 +1,12 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
-use std::fs;
-use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, SecondsFormat, TimeDelta, Timelike, Utc};
+use flate2::read::GzDecoder;
 
 #[derive(Parser)]
 #[command(name = "log-analyzer")]
@@ -22,6 +24,22 @@ enum Commands {
         input: String,
         /// Output JSON file path
         output: String,
+        /// Stop after this many entries instead of reading the whole file
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Write per-IP sessions instead of entries, splitting on gaps longer than this (e.g. 30m, 1h, 90s)
+        #[arg(long, value_name = "GAP", value_parser = parse_gap)]
+        sessionize: Option<TimeDelta>,
+        /// Keep only requests whose path matches this regex, e.g. "^/api/"
+        #[arg(long, value_name = "REGEX", value_parser = Regex::new)]
+        path_filter: Option<Regex>,
+        /// Write entries in chronological order; ones with unparseable timestamps go last
+        #[arg(long)]
+        sort_by_time: bool,
+        /// MaxMind GeoLite2 City database used to add country and city to each entry
+        #[cfg(feature = "geoip")]
+        #[arg(long, value_name = "PATH")]
+        geoip_db: Option<String>,
     },
     /// Parse JSON logs
     JsonLog {
@@ -32,6 +50,13 @@ enum Commands {
         /// Filter by log level
         #[arg(long)]
         level: Option<String>,
+        /// Stop after this many entries that pass the filters
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Parse timestamps as rfc3339, epoch, epoch_ms, or a strftime pattern such as
+        /// "%Y-%m-%d %H:%M:%S", and rewrite them as RFC 3339 UTC
+        #[arg(long, value_name = "FORMAT", value_parser = parse_time_format)]
+        time_format: Option<TimeFormat>,
     },
     /// Extract error patterns
     Errors {
@@ -42,6 +67,13 @@ enum Commands {
         /// Custom error pattern (regex)
         #[arg(long)]
         pattern: Option<String>,
+        /// Stop after this many error entries
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Parse a dedicated server error log into level, PID, and connection fields;
+        /// lines that don't fit fall back to pattern matching
+        #[arg(long, value_enum)]
+        error_format: Option<ErrorFormat>,
     },
     /// Generate log statistics
     Stats {
@@ -50,13 +82,76 @@ enum Commands {
         /// Log format: access, json, or auto
         #[arg(long, default_value = "auto")]
         format: String,
+        /// Report access-log requests that took longer than this many milliseconds
+        #[arg(long, value_name = "MS")]
+        slow_threshold: Option<f64>,
+        /// Whitespace-separated field holding the request duration, counting from 1
+        /// (default: the first field after the Combined Log Format's user agent)
+        #[arg(long, value_name = "N", requires = "slow_threshold")]
+        duration_column: Option<usize>,
+        /// Unit of the duration field; Nginx's $request_time is in seconds
+        #[arg(long, value_enum, default_value_t = DurationUnit::Seconds)]
+        duration_unit: DurationUnit,
+        /// How many of the slowest requests to list
+        #[arg(long, value_name = "N", default_value_t = 5)]
+        slow_top: usize,
+        /// Write the access-log stats to this path as a markdown report instead of printing them
+        #[arg(long, value_name = "PATH")]
+        markdown: Option<String>,
+        /// Chart bytes served by hour of day (in the log's own time zone)
+        #[arg(long)]
+        bandwidth_by_hour: bool,
+        /// Only count access-log requests whose path matches this regex, e.g. "^/api/"
+        #[arg(long, value_name = "REGEX", value_parser = Regex::new)]
+        path_filter: Option<Regex>,
+        /// How many access-log client IPs and paths to rank by request count
+        #[arg(long, value_name = "N", default_value_t = DEFAULT_TOP)]
+        top: usize,
     },
 }
 
+/// How JSON log timestamps are written, for --time-format
+#[derive(Debug, Clone, PartialEq)]
+enum TimeFormat {
+    Rfc3339,
+    /// Seconds since the Unix epoch
+    Epoch,
+    /// Milliseconds since the Unix epoch
+    EpochMillis,
+    /// A chrono strftime pattern; without a %z offset the time is taken as UTC
+    Strftime(String),
+}
+
+fn parse_time_format(format: &str) -> Result<TimeFormat, String> {
+    Ok(match format {
+        "rfc3339" => TimeFormat::Rfc3339,
+        "epoch" => TimeFormat::Epoch,
+        "epoch_ms" => TimeFormat::EpochMillis,
+        pattern if pattern.contains('%') => TimeFormat::Strftime(pattern.to_string()),
+        other => return Err(format!("unknown time format '{}': use rfc3339, epoch, epoch_ms, or a strftime pattern", other)),
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ErrorFormat {
+    Nginx,
+    Apache,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DurationUnit {
+    Seconds,
+    Millis,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct AccessLogEntry {
     ip: String,
-    timestamp: String,
+    /// Request time, written as RFC 3339; `None` when the log's timestamp isn't Common Log Format
+    timestamp: Option<DateTime<FixedOffset>>,
+    /// The original timestamp, kept when it couldn't be parsed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timestamp_raw: Option<String>,
     method: String,
     path: String,
     http_version: String,
@@ -64,6 +159,11 @@ struct AccessLogEntry {
     response_size: Option<u64>,
     referer: Option<String>,
     user_agent: Option<String>,
+    /// Filled in by --geoip-db lookups
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    country: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    city: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -71,6 +171,11 @@ struct JsonLogEntry {
     timestamp: String,
     level: String,
     message: String,
+    /// The original timestamp, kept when --time-format couldn't parse it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timestamp_raw: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    timestamp_unparsed: bool,
     #[serde(flatten)]
     extra: HashMap<String, Value>,
 }
@@ -81,116 +186,397 @@ struct ErrorEntry {
     error_type: String,
     message: String,
     source_line: String,
+    /// Severity from a structured error log, e.g. "error" or "crit"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    level: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pid: Option<u32>,
+    /// Nginx's `*N` connection number
+    #[serde(skip_serializing_if = "Option::is_none")]
+    connection_id: Option<u64>,
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::AccessLog { input, output } => {
-            parse_access_logs(&input, &output)?;
+        Commands::AccessLog {
+            input,
+            output,
+            limit,
+            sessionize,
+            path_filter,
+            sort_by_time,
+            #[cfg(feature = "geoip")]
+            geoip_db,
+        } => {
+            let options = AccessLogOptions {
+                limit,
+                session_gap: sessionize,
+                path_filter,
+                sort_by_time,
+                #[cfg(feature = "geoip")]
+                geoip_db,
+            };
+            parse_access_logs(&input, &output, &options)?;
         }
-        Commands::JsonLog { input, output, level } => {
-            parse_json_logs(&input, &output, level.as_deref())?;
+        Commands::JsonLog { input, output, level, limit, time_format } => {
+            parse_json_logs(&input, &output, level.as_deref(), limit, time_format.as_ref())?;
         }
-        Commands::Errors { input, output, pattern } => {
-            extract_errors(&input, &output, pattern.as_deref())?;
+        Commands::Errors { input, output, pattern, limit, error_format } => {
+            extract_errors(&input, &output, pattern.as_deref(), limit, error_format)?;
         }
-        Commands::Stats { input, format } => {
-            generate_stats(&input, &format)?;
+        Commands::Stats { input, format, slow_threshold, duration_column, duration_unit, slow_top, markdown, bandwidth_by_hour, path_filter, top } => {
+            let options = StatsOptions {
+                slow: slow_threshold.map(|threshold_ms| SlowRequestOptions {
+                    threshold_ms,
+                    column: duration_column,
+                    unit: duration_unit,
+                    top: slow_top,
+                }),
+                markdown,
+                bandwidth_by_hour,
+                path_filter,
+                top: Some(top),
+            };
+            generate_stats(&input, &format, &options)?;
         }
     }
 
     Ok(())
 }
 
-fn parse_access_logs(input_path: &str, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let content = fs::read_to_string(input_path)?;
+/// True once `count` entries have been collected under `limit`, so the caller can stop reading
+fn limit_reached(count: usize, limit: Option<usize>) -> bool {
+    limit.is_some_and(|limit| count >= limit)
+}
+
+fn print_limit_note(count: usize, limit: Option<usize>) {
+    if limit_reached(count, limit) {
+        println!("Stopped early after {} entries (--limit)", count);
+    }
+}
+
+/// Read buffer for the line-by-line parsers; input files can be far larger than this
+const READ_BUFFER_BYTES: usize = 64 * 1024;
+
+/// First two bytes of every gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Opens a log for buffered reading, decompressing it on the fly when it is gzipped:
+/// a .gz name or the gzip magic bytes, so rotated logs work whatever they are called
+fn open_input(path: &str) -> io::Result<Box<dyn BufRead>> {
+    let mut reader = BufReader::with_capacity(READ_BUFFER_BYTES, File::open(path)?);
+    if path.ends_with(".gz") || reader.fill_buf()?.starts_with(&GZIP_MAGIC) {
+        return Ok(Box::new(BufReader::with_capacity(READ_BUFFER_BYTES, GzDecoder::new(reader))));
+    }
+    Ok(Box::new(reader))
+}
+
+/// Writes a JSON array one element at a time, so the parsers never hold every entry at once
+struct JsonArrayWriter<W: Write> {
+    out: W,
+    count: usize,
+}
+
+impl<W: Write> JsonArrayWriter<W> {
+    fn new(mut out: W) -> io::Result<Self> {
+        out.write_all(b"[")?;
+        Ok(JsonArrayWriter { out, count: 0 })
+    }
     
-    // Common Log Format regex
-    let access_log_regex = Regex::new(
-        r#"^(\S+) \S+ \S+ \[([^\]]+)\] "(\S+) (\S+) (\S+)" (\d+) (\S+)(?: "([^"]*)" "([^"]*)")?.*$"#
-    )?;
+    fn push<T: Serialize>(&mut self, item: &T) -> io::Result<()> {
+        self.out.write_all(if self.count == 0 { b"\n" } else { b",\n" })?;
+        serde_json::to_writer_pretty(&mut self.out, item)?;
+        self.count += 1;
+        Ok(())
+    }
     
+    /// Closes the array and returns how many elements were written
+    fn finish(mut self) -> io::Result<usize> {
+        self.out.write_all(if self.count == 0 { b"]\n" } else { b"\n]\n" })?;
+        self.out.flush()?;
+        Ok(self.count)
+    }
+}
+
+fn create_json_array(path: &str) -> io::Result<JsonArrayWriter<BufWriter<File>>> {
+    JsonArrayWriter::new(BufWriter::new(File::create(path)?))
+}
+
+/// Optional extras for the AccessLog command
+#[derive(Debug, Default)]
+struct AccessLogOptions {
+    limit: Option<usize>,
+    session_gap: Option<TimeDelta>,
+    path_filter: Option<Regex>,
+    sort_by_time: bool,
+    #[cfg(feature = "geoip")]
+    geoip_db: Option<String>,
+}
+
+fn parse_access_logs(input_path: &str, output_path: &str, options: &AccessLogOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let limit = options.limit;
+    let reader = open_input(input_path)?;
+    let mut output = create_json_array(output_path)?;
+    
+    #[cfg(feature = "geoip")]
+    let geoip = options.geoip_db.as_deref().map(maxminddb::Reader::open_readfile).transpose()?;
+    #[cfg(feature = "geoip")]
+    let mut per_country = BTreeMap::new();
+    
+    let access_log_regex = access_log_regex();
+    let mut parsed = 0;
+    let mut unparsed_times = 0;
+    // Sessions and sorting need every entry at once; otherwise each is written as it's parsed
+    let collect = options.session_gap.is_some() || options.sort_by_time;
     let mut entries = Vec::new();
     
-    for line in content.lines() {
-        if let Some(captures) = access_log_regex.captures(line) {
-            let entry = AccessLogEntry {
-                ip: captures.get(1).unwrap().as_str().to_string(),
-                timestamp: captures.get(2).unwrap().as_str().to_string(),
-                method: captures.get(3).unwrap().as_str().to_string(),
-                path: captures.get(4).unwrap().as_str().to_string(),
-                http_version: captures.get(5).unwrap().as_str().to_string(),
-                status_code: captures.get(6).unwrap().as_str().parse().unwrap_or(0),
-                response_size: captures.get(7)
-                    .and_then(|m| if m.as_str() == "-" { None } else { m.as_str().parse().ok() }),
-                referer: captures.get(8).map(|m| m.as_str().to_string()),
-                user_agent: captures.get(9).map(|m| m.as_str().to_string()),
-            };
+    // Reading line by line lets --limit stop without scanning the rest of the file;
+    // like the limit, the path filter applies as entries are collected
+    for line in reader.lines() {
+        if limit_reached(parsed, limit) {
+            break;
+        }
+        
+        let line = line?;
+        let Some(entry) = parse_access_line(&line, &access_log_regex) else {
+            continue;
+        };
+        if !options.path_filter.as_ref().is_none_or(|filter| filter.is_match(&entry.path)) {
+            continue;
+        }
+        
+        #[cfg(feature = "geoip")]
+        let mut entry = entry;
+        #[cfg(feature = "geoip")]
+        if let Some(reader) = &geoip {
+            *per_country.entry(geoip_lookup(&mut entry, reader)).or_insert(0) += 1;
+        }
+        
+        if let Some(raw) = &entry.timestamp_raw {
+            if unparsed_times == 0 {
+                eprintln!("⚠️  Unparseable timestamp '{}' kept as timestamp_raw", raw);
+            }
+            unparsed_times += 1;
+        }
+        
+        parsed += 1;
+        if collect {
             entries.push(entry);
+        } else {
+            output.push(&entry)?;
+        }
+    }
+    if unparsed_times > 1 {
+        eprintln!("⚠️  {} entries in all had unparseable timestamps", unparsed_times);
+    }
+    
+    #[cfg(feature = "geoip")]
+    if geoip.is_some() {
+        println!("\n🌍 Requests per country:");
+        for (country, count) in per_country {
+            println!("  {}: {}", country, count);
         }
     }
     
-    let json = serde_json::to_string_pretty(&entries)?;
-    fs::write(output_path, json)?;
+    if options.sort_by_time {
+        sort_by_time(&mut entries);
+    }
+    match options.session_gap {
+        Some(gap) => {
+            for session in sessionize(&entries, gap) {
+                output.push(&session)?;
+            }
+            println!("Grouped into {} sessions (gap > {}s starts a new one)", output.count, gap.num_seconds());
+        }
+        None => {
+            for entry in &entries {
+                output.push(entry)?;
+            }
+        }
+    }
+    output.finish()?;
     
-    println!("Parsed {} access log entries", entries.len());
+    println!("Parsed {} access log entries", parsed);
+    print_limit_note(parsed, limit);
     println!("Output written to: {}", output_path);
     
     Ok(())
 }
 
-fn parse_json_logs(input_path: &str, output_path: &str, level_filter: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
-    let content = fs::read_to_string(input_path)?;
-    let mut entries = Vec::new();
+/// Orders entries by the instant they happened, whatever their UTC offsets; the sort is
+/// stable, so same-second requests keep their log order and unparsed timestamps go last
+fn sort_by_time(entries: &mut [AccessLogEntry]) {
+    entries.sort_by_key(|entry| (entry.timestamp.is_none(), entry.timestamp));
+}
+
+/// Name used when an IP isn't in the GeoIP database, or the record lacks the field
+#[cfg(feature = "geoip")]
+const GEOIP_UNKNOWN: &str = "Unknown";
+
+/// Sets one entry's country and city from the database and returns the country
+#[cfg(feature = "geoip")]
+fn geoip_lookup<S: AsRef<[u8]>>(entry: &mut AccessLogEntry, reader: &maxminddb::Reader<S>) -> String {
+    let record = entry.ip.parse::<std::net::IpAddr>().ok()
+        .and_then(|ip| reader.lookup(ip).ok())
+        .and_then(|result| result.decode::<maxminddb::geoip2::City>().ok().flatten());
+    
+    let (country, city) = match &record {
+        Some(record) => (record.country.names.english, record.city.names.english),
+        None => (None, None),
+    };
+    let country = country.unwrap_or(GEOIP_UNKNOWN).to_string();
+    
+    entry.country = Some(country.clone());
+    entry.city = Some(city.unwrap_or(GEOIP_UNKNOWN).to_string());
+    country
+}
+
+/// Parses an inactivity gap: a number of seconds, optionally suffixed with s, m, or h
+fn parse_gap(gap: &str) -> Result<TimeDelta, String> {
+    let (number, unit_secs) = match gap.strip_suffix(['s', 'm', 'h']) {
+        Some(number) if gap.ends_with('h') => (number, 3600),
+        Some(number) if gap.ends_with('m') => (number, 60),
+        Some(number) => (number, 1),
+        None => (gap, 1),
+    };
+    // Unsigned parsing rejects negative gaps, which would make every request its own session
+    let count: u64 = number.parse().map_err(|_| format!("invalid gap '{}': expected e.g. 90s, 30m, or 1h", gap))?;
+    
+    count
+        .checked_mul(unit_secs)
+        .and_then(|secs| i64::try_from(secs).ok())
+        .and_then(TimeDelta::try_seconds)
+        .ok_or_else(|| format!("gap '{}' is too large", gap))
+}
+
+/// One visitor's run of requests with no pause longer than the session gap
+#[derive(Debug, Serialize, PartialEq)]
+struct Session {
+    ip: String,
+    start: String,
+    end: String,
+    requests: usize,
+    duration_secs: i64,
+}
+
+/// Groups each IP's requests by time and starts a new session whenever the pause between
+/// consecutive requests exceeds `gap`. Entries whose timestamp can't be parsed are skipped
+fn sessionize(entries: &[AccessLogEntry], gap: TimeDelta) -> Vec<Session> {
+    let mut by_ip: BTreeMap<&str, Vec<DateTime<FixedOffset>>> = BTreeMap::new();
+    for entry in entries {
+        if let Some(time) = entry.timestamp {
+            by_ip.entry(&entry.ip).or_default().push(time);
+        }
+    }
+    
+    let mut sessions = Vec::new();
+    for (ip, mut times) in by_ip {
+        times.sort();
+        
+        let mut start = 0;
+        for i in 1..=times.len() {
+            if i == times.len() || times[i] - times[i - 1] > gap {
+                let (first, last) = (times[start], times[i - 1]);
+                sessions.push(Session {
+                    ip: ip.to_string(),
+                    start: first.to_rfc3339(),
+                    end: last.to_rfc3339(),
+                    requests: i - start,
+                    duration_secs: (last - first).num_seconds(),
+                });
+                start = i;
+            }
+        }
+    }
+    
+    sessions
+}
+
+/// Timestamp layout inside a Common Log Format line's brackets
+const CLF_TIMESTAMP: &str = "%d/%b/%Y:%H:%M:%S %z";
+
+/// Common Log Format, with the Combined format's referer and user agent when present
+fn access_log_regex() -> Regex {
+    Regex::new(
+        r#"^(\S+) \S+ \S+ \[([^\]]+)\] "(\S+) (\S+) (\S+)" (\d+) (\S+)(?: "([^"]*)" "([^"]*)")?.*$"#
+    ).unwrap()
+}
+
+fn parse_access_line(line: &str, access_log_regex: &Regex) -> Option<AccessLogEntry> {
+    let captures = access_log_regex.captures(line)?;
+    let raw_timestamp = captures.get(2).unwrap().as_str();
+    let timestamp = DateTime::parse_from_str(raw_timestamp, CLF_TIMESTAMP).ok();
     
-    for line in content.lines() {
+    Some(AccessLogEntry {
+        ip: captures.get(1).unwrap().as_str().to_string(),
+        timestamp,
+        timestamp_raw: timestamp.is_none().then(|| raw_timestamp.to_string()),
+        method: captures.get(3).unwrap().as_str().to_string(),
+        path: captures.get(4).unwrap().as_str().to_string(),
+        http_version: captures.get(5).unwrap().as_str().to_string(),
+        status_code: captures.get(6).unwrap().as_str().parse().unwrap_or(0),
+        response_size: captures.get(7)
+            .and_then(|m| if m.as_str() == "-" { None } else { m.as_str().parse().ok() }),
+        referer: captures.get(8).map(|m| m.as_str().to_string()),
+        user_agent: captures.get(9).map(|m| m.as_str().to_string()),
+        country: None,
+        city: None,
+    })
+}
+
+fn parse_json_logs(input_path: &str, output_path: &str, level_filter: Option<&str>, limit: Option<usize>, time_format: Option<&TimeFormat>) -> Result<(), Box<dyn std::error::Error>> {
+    let reader = open_input(input_path)?;
+    let mut output = create_json_array(output_path)?;
+    
+    // The limit counts entries that survive the level filter
+    for line in reader.lines() {
+        if limit_reached(output.count, limit) {
+            break;
+        }
+        
+        let line = line?;
         if line.trim().is_empty() {
             continue;
         }
         
-        match serde_json::from_str::<JsonLogEntry>(line) {
-            Ok(entry) => {
-                if let Some(filter_level) = level_filter {
-                    if entry.level.to_lowercase() == filter_level.to_lowercase() {
-                        entries.push(entry);
-                    }
-                } else {
-                    entries.push(entry);
-                }
-            }
+        let mut entry = match serde_json::from_str::<JsonLogEntry>(&line) {
+            Ok(entry) => entry,
             Err(_) => {
                 // Try to parse as generic JSON and convert
-                if let Ok(value) = serde_json::from_str::<Value>(line) {
-                    let entry = JsonLogEntry {
-                        timestamp: extract_field(&value, "timestamp", "time", "@timestamp")
-                            .unwrap_or_else(|| "unknown".to_string()),
-                        level: extract_field(&value, "level", "severity", "loglevel")
-                            .unwrap_or_else(|| "info".to_string()),
-                        message: extract_field(&value, "message", "msg", "text")
-                            .unwrap_or_else(|| "".to_string()),
-                        extra: value.as_object().unwrap_or(&serde_json::Map::new()).clone().into_iter()
-                            .filter(|(k, _)| !["timestamp", "time", "@timestamp", "level", "severity", "loglevel", "message", "msg", "text"].contains(&k.as_str()))
-                            .collect(),
-                    };
-                    
-                    if let Some(filter_level) = level_filter {
-                        if entry.level.to_lowercase() == filter_level.to_lowercase() {
-                            entries.push(entry);
-                        }
-                    } else {
-                        entries.push(entry);
-                    }
+                let Ok(value) = serde_json::from_str::<Value>(&line) else {
+                    continue;
+                };
+                JsonLogEntry {
+                    timestamp: extract_field(&value, "timestamp", "time", "@timestamp")
+                        .unwrap_or_else(|| "unknown".to_string()),
+                    level: extract_field(&value, "level", "severity", "loglevel")
+                        .unwrap_or_else(|| "info".to_string()),
+                    message: extract_field(&value, "message", "msg", "text")
+                        .unwrap_or_default(),
+                    timestamp_raw: None,
+                    timestamp_unparsed: false,
+                    extra: value.as_object().unwrap_or(&serde_json::Map::new()).clone().into_iter()
+                        .filter(|(k, _)| !["timestamp", "time", "@timestamp", "level", "severity", "loglevel", "message", "msg", "text"].contains(&k.as_str()))
+                        .collect(),
                 }
             }
+        };
+        
+        if level_filter.is_some_and(|filter_level| entry.level.to_lowercase() != filter_level.to_lowercase()) {
+            continue;
         }
+        
+        if let Some(format) = time_format {
+            normalize_timestamp(&mut entry, format);
+        }
+        output.push(&entry)?;
     }
+    let parsed = output.finish()?;
     
-    let json = serde_json::to_string_pretty(&entries)?;
-    fs::write(output_path, json)?;
-    
-    println!("Parsed {} JSON log entries", entries.len());
+    println!("Parsed {} JSON log entries", parsed);
+    print_limit_note(parsed, limit);
     if let Some(level) = level_filter {
         println!("Filtered by level: {}", level);
     }
@@ -199,53 +585,123 @@ fn parse_json_logs(input_path: &str, output_path: &str, level_filter: Option<&st
     Ok(())
 }
 
+/// Reads the first field present as a string; numbers (such as epoch timestamps) are rendered
 fn extract_field(value: &Value, fields: &str, alt1: &str, alt2: &str) -> Option<String> {
     value.get(fields)
         .or_else(|| value.get(alt1))
         .or_else(|| value.get(alt2))
-        .and_then(|v| v.as_str())
-        .map(|s| s.to_string())
+        .and_then(|v| match v {
+            Value::String(s) => Some(s.clone()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        })
 }
 
-fn extract_errors(input_path: &str, output_path: &str, custom_pattern: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
-    let content = fs::read_to_string(input_path)?;
+fn parse_timestamp(raw: &str, format: &TimeFormat) -> Option<DateTime<Utc>> {
+    match format {
+        TimeFormat::Rfc3339 => DateTime::parse_from_rfc3339(raw).ok().map(|time| time.with_timezone(&Utc)),
+        TimeFormat::Epoch => DateTime::from_timestamp(raw.parse().ok()?, 0),
+        TimeFormat::EpochMillis => DateTime::from_timestamp_millis(raw.parse().ok()?),
+        TimeFormat::Strftime(pattern) => DateTime::parse_from_str(raw, pattern)
+            .map(|time| time.with_timezone(&Utc))
+            .or_else(|_| NaiveDateTime::parse_from_str(raw, pattern).map(|time| time.and_utc()))
+            .ok(),
+    }
+}
+
+/// Rewrites the timestamp as RFC 3339 UTC, or flags it and keeps a raw copy if it won't parse
+fn normalize_timestamp(entry: &mut JsonLogEntry, format: &TimeFormat) {
+    match parse_timestamp(&entry.timestamp, format) {
+        Some(time) => entry.timestamp = time.to_rfc3339_opts(SecondsFormat::Millis, true),
+        None => {
+            entry.timestamp_raw = Some(entry.timestamp.clone());
+            entry.timestamp_unparsed = true;
+        }
+    }
+}
+
+fn extract_errors(input_path: &str, output_path: &str, custom_pattern: Option<&str>, limit: Option<usize>, error_format: Option<ErrorFormat>) -> Result<(), Box<dyn std::error::Error>> {
+    let reader = open_input(input_path)?;
+    let structured = error_format.map(error_log_regex);
     
     let error_patterns = if let Some(pattern) = custom_pattern {
         vec![Regex::new(pattern)?]
     } else {
         vec![
-            Regex::new(r"(?i)(error|exception|fail|fatal|panic|crash)"?)?,
+            Regex::new(r"(?i)(error|exception|fail|fatal|panic|crash)")?,
             Regex::new(r"\d{4}-\d{2}-\d{2}.*?(ERROR|FATAL|EXCEPTION)")?,
             Regex::new(r"(?i)(stack trace|traceback|backtrace)")?,
         ]
     };
     
-    let mut errors = Vec::new();
+    let mut errors = create_json_array(output_path)?;
     
-    for line in content.lines() {
+    for line in reader.lines() {
+        if limit_reached(errors.count, limit) {
+            break;
+        }
+        
+        let line = line?;
+        if let Some(entry) = structured.as_ref().and_then(|regex| parse_error_log_line(&line, regex)) {
+            errors.push(&entry)?;
+            continue;
+        }
+        
         for pattern in &error_patterns {
-            if pattern.is_match(line) {
+            if pattern.is_match(&line) {
                 let error = ErrorEntry {
-                    timestamp: extract_timestamp_from_line(line).unwrap_or_else(|| "unknown".to_string()),
-                    error_type: classify_error_type(line),
-                    message: extract_error_message(line),
-                    source_line: line.to_string(),
+                    timestamp: extract_timestamp_from_line(&line).unwrap_or_else(|| "unknown".to_string()),
+                    error_type: classify_error_type(&line),
+                    message: extract_error_message(&line),
+                    source_line: line.clone(),
+                    level: None,
+                    pid: None,
+                    connection_id: None,
                 };
-                errors.push(error);
+                errors.push(&error)?;
                 break; // Don't match the same line multiple times
             }
         }
     }
+    let extracted = errors.finish()?;
     
-    let json = serde_json::to_string_pretty(&errors)?;
-    fs::write(output_path, json)?;
-    
-    println!("Extracted {} error entries", errors.len());
+    println!("Extracted {} error entries", extracted);
+    print_limit_note(extracted, limit);
     println!("Output written to: {}", output_path);
     
     Ok(())
 }
 
+/// Line layout of each server's error log, with named groups for the structured fields
+fn error_log_regex(format: ErrorFormat) -> Regex {
+    let pattern = match format {
+        // 2024/01/02 15:04:05 [error] 12345#0: *67 message
+        ErrorFormat::Nginx => {
+            r"^(?P<time>\d{4}/\d{2}/\d{2} \d{2}:\d{2}:\d{2}) \[(?P<level>\w+)\] (?P<pid>\d+)#\d+: (?:\*(?P<conn>\d+) )?(?P<message>.*)$"
+        }
+        // [Wed Oct 11 14:32:52.123456 2000] [core:error] [pid 35708:tid 4328636416] [client 1.2.3.4:80] message
+        ErrorFormat::Apache => {
+            r"^\[(?P<time>[^\]]+)\] \[(?:[\w-]+:)?(?P<level>\w+)\] (?:\[pid (?P<pid>\d+)[^\]]*\] )?(?:\[client [^\]]+\] )?(?P<message>.*)$"
+        }
+    };
+    Regex::new(pattern).unwrap()
+}
+
+fn parse_error_log_line(line: &str, regex: &Regex) -> Option<ErrorEntry> {
+    let captures = regex.captures(line)?;
+    let message = captures["message"].to_string();
+    
+    Some(ErrorEntry {
+        timestamp: captures["time"].to_string(),
+        error_type: classify_error_type(&message),
+        message,
+        source_line: line.to_string(),
+        level: Some(captures["level"].to_string()),
+        pid: captures.name("pid").and_then(|pid| pid.as_str().parse().ok()),
+        connection_id: captures.name("conn").and_then(|conn| conn.as_str().parse().ok()),
+    })
+}
+
 fn extract_timestamp_from_line(line: &str) -> Option<String> {
     let timestamp_regex = Regex::new(r"\d{4}-\d{2}-\d{2}[T ]\d{2}:\d{2}:\d{2}").ok()?;
     timestamp_regex.find(line).map(|m| m.as_str().to_string())
@@ -280,22 +736,73 @@ fn extract_error_message(line: &str) -> String {
     }
 }
 
-fn generate_stats(input_path: &str, format: &str) -> Result<(), Box<dyn std::error::Error>> {
-    let content = fs::read_to_string(input_path)?;
+/// Optional extras for the Stats command
+#[derive(Debug, Default)]
+struct StatsOptions {
+    slow: Option<SlowRequestOptions>,
+    /// Write a markdown report here instead of printing to the terminal
+    markdown: Option<String>,
+    bandwidth_by_hour: bool,
+    path_filter: Option<Regex>,
+    /// How many IPs and paths to rank; None means DEFAULT_TOP
+    top: Option<usize>,
+}
+
+/// Where an extended access log keeps each request's duration, and what counts as slow
+#[derive(Debug, Clone, Copy)]
+struct SlowRequestOptions {
+    threshold_ms: f64,
+    /// 1-based whitespace-separated field; None means right after the Combined Log Format fields
+    column: Option<usize>,
+    unit: DurationUnit,
+    top: usize,
+}
+
+#[derive(Debug, PartialEq)]
+struct SlowRequest {
+    path: String,
+    duration_ms: f64,
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct SlowReport {
+    /// Requests that had a duration at all
+    timed: usize,
+    /// Requests over the threshold
+    slow: usize,
+    /// The slowest of those, slowest first
+    slowest: Vec<SlowRequest>,
+}
+
+fn generate_stats(input_path: &str, format: &str, options: &StatsOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let mut content = String::new();
+    open_input(input_path)?.read_to_string(&mut content)?;
     let lines: Vec<&str> = content.lines().collect();
+    // Taken from the disk, since a gzipped log's content is larger than the file
+    let file_size = fs::metadata(input_path)?.len();
+    
+    if let Some(markdown_path) = &options.markdown {
+        if format != "access" && !(format == "auto" && looks_like_access_log(&lines)) {
+            return Err("--markdown reports are only available for access logs".into());
+        }
+        let stats = compute_access_stats(&lines, options);
+        fs::write(markdown_path, access_stats_markdown(input_path, lines.len(), file_size, &stats))?;
+        println!("Markdown report written to: {}", markdown_path);
+        return Ok(());
+    }
     
     println!("📊 Log Statistics for: {}", input_path);
     println!("Total lines: {}", lines.len());
-    println!("File size: {} bytes", content.len());
+    println!("File size: {} bytes", file_size);
     
     match format {
-        "access" => analyze_access_log_stats(&lines),
+        "access" => print_access_stats(&compute_access_stats(&lines, options), options),
         "json" => analyze_json_log_stats(&lines),
         "auto" => {
             // Try to detect format
-            if lines.iter().any(|line| line.contains("GET ") || line.contains("POST ")) {
+            if looks_like_access_log(&lines) {
                 println!("Detected format: Access Log");
-                analyze_access_log_stats(&lines);
+                print_access_stats(&compute_access_stats(&lines, options), options);
             } else if lines.iter().any(|line| line.trim_start().starts_with('{')) {
                 println!("Detected format: JSON Log");
                 analyze_json_log_stats(&lines);
@@ -310,33 +817,434 @@ fn generate_stats(input_path: &str, format: &str) -> Result<(), Box<dyn std::err
     Ok(())
 }
 
-fn analyze_access_log_stats(lines: &[&str]) {
-    let mut status_codes = HashMap::new();
-    let mut methods = HashMap::new();
+fn looks_like_access_log(lines: &[&str]) -> bool {
+    lines.iter().any(|line| line.contains("GET ") || line.contains("POST "))
+}
+
+/// Methods from RFC 9110 plus PATCH; anything else in that position is tallied as OTHER
+const KNOWN_METHODS: [&str; 9] = ["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD", "OPTIONS", "TRACE", "CONNECT"];
+
+const OTHER_METHOD: &str = "OTHER";
+
+/// Share of OTHER methods above which the log probably isn't in the expected format
+const OTHER_METHOD_WARN_SHARE: f64 = 0.1;
+
+/// How many client IPs and paths the stats rank unless --top says otherwise
+const DEFAULT_TOP: usize = 5;
+
+/// Everything the Stats command reports about an access log, computed once so the
+/// terminal and markdown outputs can't disagree
+#[derive(Debug, Default)]
+struct AccessStats {
+    /// Unrecognized methods are counted under OTHER
+    methods: BTreeMap<String, usize>,
+    /// Every status code, most frequent first
+    status_codes: Vec<(String, usize)>,
+    /// Busiest first
+    top_ips: Vec<(String, usize)>,
+    /// Most requested first
+    top_paths: Vec<(String, usize)>,
+    client_errors: usize,
+    server_errors: usize,
+    /// Earliest and latest parseable timestamps
+    time_range: Option<(String, String)>,
+    bytes: ByteTotals,
+    agents: AgentBreakdown,
+    slow: Option<SlowReport>,
+    /// Bytes served in each hour of the day, 00 through 23
+    bandwidth_by_hour: Option<[u64; 24]>,
+}
+
+fn compute_access_stats(lines: &[&str], options: &StatsOptions) -> AccessStats {
+    let mut stats = AccessStats::default();
     
-    let access_log_regex = Regex::new(
+    // Filtering the lines up front keeps every aggregate below consistent with the filter
+    let filtered: Vec<&str>;
+    let lines = match &options.path_filter {
+        Some(filter) => {
+            let path_regex = Regex::new(r#""\S+ (\S+) \S+""#).unwrap();
+            filtered = lines
+                .iter()
+                .copied()
+                .filter(|line| path_regex.captures(line).is_some_and(|captures| filter.is_match(&captures[1])))
+                .collect();
+            &filtered[..]
+        }
+        None => lines,
+    };
+    
+    let request_regex = Regex::new(
         r#""(\S+) \S+ \S+" (\d+)"#
     ).unwrap();
     
+    let mut status_codes: HashMap<&str, usize> = HashMap::new();
     for line in lines {
-        if let Some(captures) = access_log_regex.captures(line) {
+        if let Some(captures) = request_regex.captures(line) {
             let method = captures.get(1).unwrap().as_str();
             let status = captures.get(2).unwrap().as_str();
             
-            *methods.entry(method.to_string()).or_insert(0) += 1;
-            *status_codes.entry(status.to_string()).or_insert(0) += 1;
+            let method = if KNOWN_METHODS.contains(&method) { method } else { OTHER_METHOD };
+            *stats.methods.entry(method.to_string()).or_insert(0) += 1;
+            *status_codes.entry(status).or_insert(0) += 1;
         }
     }
+    stats.status_codes = ranked(status_codes);
+    
+    let parser = access_log_regex();
+    let entries: Vec<AccessLogEntry> = lines.iter().filter_map(|line| parse_access_line(line, &parser)).collect();
+    
+    let mut ips: HashMap<&str, usize> = HashMap::new();
+    let mut paths: HashMap<&str, usize> = HashMap::new();
+    for entry in &entries {
+        *ips.entry(&entry.ip).or_insert(0) += 1;
+        *paths.entry(&entry.path).or_insert(0) += 1;
+        match entry.status_code {
+            400..=499 => stats.client_errors += 1,
+            500..=599 => stats.server_errors += 1,
+            _ => {}
+        }
+    }
+    let top = options.top.unwrap_or(DEFAULT_TOP);
+    stats.top_ips = ranked(ips);
+    stats.top_ips.truncate(top);
+    stats.top_paths = ranked(paths);
+    stats.top_paths.truncate(top);
+    
+    let times = entries.iter().filter_map(|entry| entry.timestamp);
+    stats.time_range = times.fold(None, |range, time| match range {
+        None => Some((time, time)),
+        Some((first, last)) => Some((first.min(time), last.max(time))),
+    }).map(|(first, last)| (first.to_rfc3339(), last.to_rfc3339()));
+    
+    stats.bytes = byte_totals(&entries);
+    stats.agents = agent_breakdown(&entries);
+    stats.slow = options.slow.as_ref().map(|slow| find_slow_requests(lines, slow));
+    stats.bandwidth_by_hour = options.bandwidth_by_hour.then(|| bandwidth_by_hour(&entries));
+    stats
+}
+
+/// Sorts counts busiest first; ties go to the smaller key so the output is deterministic
+fn ranked(counts: HashMap<&str, usize>) -> Vec<(String, usize)> {
+    let mut ranked: Vec<(String, usize)> = counts.into_iter().map(|(key, count)| (key.to_string(), count)).collect();
+    ranked.sort_by(|(key_a, a), (key_b, b)| b.cmp(a).then_with(|| key_a.cmp(key_b)));
+    ranked
+}
+
+/// Sums response sizes into hour-of-day buckets, using the hour as the log wrote it.
+/// Entries with an unparseable timestamp are left out; quiet hours stay at zero
+fn bandwidth_by_hour(entries: &[AccessLogEntry]) -> [u64; 24] {
+    let mut hours = [0; 24];
+    
+    for entry in entries {
+        if let Some(time) = entry.timestamp {
+            hours[time.hour() as usize] += entry.response_size.unwrap_or(0);
+        }
+    }
+    
+    hours
+}
+
+/// Width of the longest bar in the hourly bandwidth chart
+const CHART_WIDTH: usize = 40;
+
+fn print_bandwidth_chart(hours: &[u64; 24]) {
+    let peak = hours.iter().copied().max().unwrap_or(0).max(1);
+    
+    println!("\n🕐 Bandwidth by Hour:");
+    for (hour, bytes) in hours.iter().enumerate() {
+        let bar = "#".repeat((*bytes as f64 / peak as f64 * CHART_WIDTH as f64).round() as usize);
+        println!("  {:02}:00 {:<width$} {}", hour, bar, format_bytes(*bytes), width = CHART_WIDTH);
+    }
+}
+
+impl AccessStats {
+    /// Fraction of requests whose method wasn't a known HTTP method
+    fn other_method_share(&self) -> f64 {
+        let total: usize = self.methods.values().sum();
+        let other = self.methods.get(OTHER_METHOD).copied().unwrap_or(0);
+        other as f64 / total.max(1) as f64
+    }
     
+    fn method_format_warning(&self) -> Option<String> {
+        let share = self.other_method_share();
+        (share > OTHER_METHOD_WARN_SHARE).then(|| {
+            format!("{:.0}% of requests have an unrecognized method; the log may not be in Common/Combined format", share * 100.0)
+        })
+    }
+}
+
+fn print_access_stats(stats: &AccessStats, options: &StatsOptions) {
     println!("\n🌐 HTTP Methods:");
-    for (method, count) in methods {
+    for (method, count) in &stats.methods {
         println!("  {}: {}", method, count);
     }
+    if let Some(warning) = stats.method_format_warning() {
+        println!("  ⚠️ {}", warning);
+    }
     
     println!("\n📈 Status Codes:");
-    for (status, count) in status_codes {
+    for (status, count) in &stats.status_codes {
         println!("  {}: {}", status, count);
     }
+    println!("  Client errors (4xx): {}", stats.client_errors);
+    println!("  Server errors (5xx): {}", stats.server_errors);
+    
+    println!("\n🖥️ Top IPs:");
+    for (ip, count) in &stats.top_ips {
+        println!("  {}: {}", ip, count);
+    }
+    
+    println!("\n🔗 Top Paths:");
+    for (path, count) in &stats.top_paths {
+        println!("  {}: {}", path, count);
+    }
+    
+    if let Some((first, last)) = &stats.time_range {
+        println!("\n⏰ Time Range:");
+        println!("  First: {}", first);
+        println!("  Last: {}", last);
+    }
+    
+    let bytes = &stats.bytes;
+    println!("\n📦 Bytes Served: {}", format_bytes(bytes.total));
+    for (class, total) in &bytes.by_status_class {
+        println!("  {}: {}", class, format_bytes(*total));
+    }
+    println!("  Top paths by bytes:");
+    for (path, total) in bytes.by_path.iter().take(TOP_PATHS) {
+        println!("    {:>10}  {}", format_bytes(*total), path);
+    }
+    
+    let agents = &stats.agents;
+    if agents.humans + agents.bots > 0 {
+        println!("\n🤖 User Agents:");
+        for (family, count) in agents.top_families() {
+            println!("  {}: {}", family, count);
+        }
+        println!("  Human vs bot: {} / {} ({:.1}% bots)", agents.humans, agents.bots, agents.bot_percent());
+    }
+    
+    if let Some(hours) = &stats.bandwidth_by_hour {
+        print_bandwidth_chart(hours);
+    }
+    
+    if let (Some(report), Some(slow)) = (&stats.slow, &options.slow) {
+        println!("\n🐢 Slow Requests (over {} ms):", slow.threshold_ms);
+        println!("  {} of {} timed requests", report.slow, report.timed);
+        for request in &report.slowest {
+            println!("  {:>10.1} ms  {}", request.duration_ms, request.path);
+        }
+    }
+}
+
+/// Renders the access-log stats as a markdown report with a table per breakdown
+fn access_stats_markdown(input_path: &str, total_lines: usize, file_size: u64, stats: &AccessStats) -> String {
+    let mut md = format!("# Log Statistics: {}\n\n", input_path);
+    md += &format!("- **Total lines:** {}\n", total_lines);
+    md += &format!("- **File size:** {}\n", format_bytes(file_size));
+    md += &format!("- **Bytes served:** {}\n", format_bytes(stats.bytes.total));
+    if let Some((first, last)) = &stats.time_range {
+        md += &format!("- **Time range:** {} to {}\n", first, last);
+    }
+    
+    let table = |md: &mut String, title: &str, headers: (&str, &str), rows: Vec<(String, String)>| {
+        *md += &format!("\n## {}\n\n| {} | {} |\n|---|---:|\n", title, headers.0, headers.1);
+        for (key, value) in rows {
+            *md += &format!("| {} | {} |\n", key, value);
+        }
+    };
+    
+    table(&mut md, "Top IPs", ("IP", "Requests"),
+          stats.top_ips.iter().map(|(ip, count)| (ip.clone(), count.to_string())).collect());
+    table(&mut md, "Top Paths", ("Path", "Requests"),
+          stats.top_paths.iter().map(|(path, count)| (format!("`{}`", path), count.to_string())).collect());
+    table(&mut md, "Status Codes", ("Status", "Requests"),
+          stats.status_codes.iter().map(|(status, count)| (status.clone(), count.to_string())).collect());
+    table(&mut md, "Errors", ("Class", "Requests"), vec![
+        ("Client errors (4xx)".to_string(), stats.client_errors.to_string()),
+        ("Server errors (5xx)".to_string(), stats.server_errors.to_string()),
+    ]);
+    table(&mut md, "HTTP Methods", ("Method", "Requests"),
+          stats.methods.iter().map(|(method, count)| (method.clone(), count.to_string())).collect());
+    if let Some(warning) = stats.method_format_warning() {
+        md += &format!("\n> **Warning:** {}\n", warning);
+    }
+    table(&mut md, "Top Paths by Bytes", ("Path", "Bytes"),
+          stats.bytes.by_path.iter().take(TOP_PATHS).map(|(path, total)| (format!("`{}`", path), format_bytes(*total))).collect());
+    
+    if let Some(hours) = &stats.bandwidth_by_hour {
+        table(&mut md, "Bandwidth by Hour", ("Hour", "Bytes"),
+              hours.iter().enumerate().map(|(hour, bytes)| (format!("{:02}:00", hour), format_bytes(*bytes))).collect());
+    }
+    
+    if let Some(report) = &stats.slow {
+        table(&mut md, "Slowest Requests", ("Path", "Duration (ms)"),
+              report.slowest.iter().map(|r| (format!("`{}`", r.path), format!("{:.1}", r.duration_ms))).collect());
+    }
+    
+    md
+}
+
+/// How many paths the bandwidth breakdown lists
+const TOP_PATHS: usize = 5;
+
+#[derive(Debug, Default, PartialEq)]
+struct ByteTotals {
+    total: u64,
+    /// Keyed by status class such as "2xx"
+    by_status_class: BTreeMap<String, u64>,
+    /// Heaviest paths first
+    by_path: Vec<(String, u64)>,
+}
+
+/// Sums response sizes; a "-" size (no body) counts as zero bytes
+fn byte_totals(entries: &[AccessLogEntry]) -> ByteTotals {
+    let mut totals = ByteTotals::default();
+    let mut by_path: HashMap<&str, u64> = HashMap::new();
+    
+    for entry in entries {
+        let size = entry.response_size.unwrap_or(0);
+        totals.total += size;
+        *totals.by_status_class.entry(format!("{}xx", entry.status_code / 100)).or_insert(0) += size;
+        *by_path.entry(&entry.path).or_insert(0) += size;
+    }
+    
+    totals.by_path = by_path.into_iter().map(|(path, total)| (path.to_string(), total)).collect();
+    totals.by_path.sort_by(|(path_a, a), (path_b, b)| b.cmp(a).then_with(|| path_a.cmp(path_b)));
+    totals
+}
+
+/// Formats a byte count with binary units, e.g. "1.5 MiB"
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+/// How many user-agent families the breakdown lists
+const TOP_AGENTS: usize = 5;
+
+/// Lowercase fragments that mark crawlers and scripted clients
+const BOT_MARKERS: [&str; 7] = ["bot", "crawler", "spider", "curl", "wget", "python-requests", "go-http-client"];
+
+/// Browser product tokens, checked in order since Chrome also claims to be Safari
+const BROWSER_TOKENS: [(&str, &str); 5] = [
+    ("Edg/", "Edge"),
+    ("OPR/", "Opera"),
+    ("Firefox/", "Firefox"),
+    ("Chrome/", "Chrome"),
+    ("Safari/", "Safari"),
+];
+
+/// Name of the family the whole "bot" bucket is tallied under
+const BOT_FAMILY: &str = "bot";
+
+#[derive(Debug, Default)]
+struct AgentBreakdown {
+    families: HashMap<String, usize>,
+    humans: usize,
+    bots: usize,
+}
+
+impl AgentBreakdown {
+    /// The most common families, most frequent first
+    fn top_families(&self) -> Vec<(&str, usize)> {
+        let mut families: Vec<_> = self.families.iter().map(|(name, count)| (name.as_str(), *count)).collect();
+        families.sort_by(|(name_a, a), (name_b, b)| b.cmp(a).then_with(|| name_a.cmp(name_b)));
+        families.truncate(TOP_AGENTS);
+        families
+    }
+    
+    fn bot_percent(&self) -> f64 {
+        self.bots as f64 * 100.0 / (self.humans + self.bots).max(1) as f64
+    }
+}
+
+/// Buckets a user agent into a simplified family: "bot" for obvious crawlers and scripts,
+/// a browser name when one is recognizable, otherwise the first product token's name
+fn agent_family(user_agent: &str) -> String {
+    let lower = user_agent.to_lowercase();
+    if BOT_MARKERS.iter().any(|marker| lower.contains(marker)) {
+        return BOT_FAMILY.to_string();
+    }
+    
+    if let Some((_, browser)) = BROWSER_TOKENS.iter().find(|(token, _)| user_agent.contains(token)) {
+        return browser.to_string();
+    }
+    
+    let product = user_agent.split_whitespace().next().unwrap_or("");
+    product.split('/').next().unwrap_or(product).to_string()
+}
+
+/// Tallies agent families; entries without a user agent (or with "-") are left out
+fn agent_breakdown(entries: &[AccessLogEntry]) -> AgentBreakdown {
+    let mut breakdown = AgentBreakdown::default();
+    
+    for user_agent in entries.iter().filter_map(|entry| entry.user_agent.as_deref()) {
+        if user_agent.is_empty() || user_agent == "-" {
+            continue;
+        }
+        
+        let family = agent_family(user_agent);
+        if family == BOT_FAMILY {
+            breakdown.bots += 1;
+        } else {
+            breakdown.humans += 1;
+        }
+        *breakdown.families.entry(family).or_insert(0) += 1;
+    }
+    
+    breakdown
+}
+
+/// Reads the request duration from the configured field, in milliseconds. Lines where
+/// that field is missing or not a number (plain Common Log Format, say) yield None
+fn request_duration_ms(line: &str, combined_regex: &Regex, column: Option<usize>, unit: DurationUnit) -> Option<f64> {
+    let field = match column {
+        Some(column) => line.split_whitespace().nth(column.checked_sub(1)?)?,
+        None => {
+            let combined = combined_regex.find(line)?;
+            line[combined.end()..].split_whitespace().next()?
+        }
+    };
+    let value: f64 = field.trim_matches('"').parse().ok()?;
+    
+    match unit {
+        DurationUnit::Seconds => Some(value * 1000.0),
+        DurationUnit::Millis => Some(value),
+    }
+}
+
+fn find_slow_requests(lines: &[&str], options: &SlowRequestOptions) -> SlowReport {
+    let request_regex = Regex::new(r#""\S+ (\S+) \S+""#).unwrap();
+    let combined_regex = Regex::new(r#"^\S+ \S+ \S+ \[[^\]]+\] "[^"]*" \d+ \S+ "[^"]*" "[^"]*""#).unwrap();
+    let mut report = SlowReport::default();
+    
+    for line in lines {
+        let duration_ms = request_duration_ms(line, &combined_regex, options.column, options.unit);
+        let (Some(captures), Some(duration_ms)) = (request_regex.captures(line), duration_ms) else {
+            continue;
+        };
+        report.timed += 1;
+        
+        if duration_ms > options.threshold_ms {
+            report.slow += 1;
+            report.slowest.push(SlowRequest { path: captures[1].to_string(), duration_ms });
+        }
+    }
+    
+    report.slowest.sort_by(|a, b| b.duration_ms.total_cmp(&a.duration_ms));
+    report.slowest.truncate(options.top);
+    report
 }
 
 fn analyze_json_log_stats(lines: &[&str]) {
@@ -350,7 +1258,7 @@ fn analyze_json_log_stats(lines: &[&str]) {
             }
             
             if let Some(timestamp) = value.get("timestamp").and_then(|v| v.as_str()) {
-                timestamps.push(timestamp);
+                timestamps.push(timestamp.to_string());
             }
         }
     }
@@ -362,8 +1270,8 @@ fn analyze_json_log_stats(lines: &[&str]) {
     
     if !timestamps.is_empty() {
         println!("\n⏰ Time Range:");
-        println!("  First: {}", timestamps.first().unwrap_or(&"unknown"));
-        println!("  Last: {}", timestamps.last().unwrap_or(&"unknown"));
+        println!("  First: {}", timestamps.first().map_or("unknown", String::as_str));
+        println!("  Last: {}", timestamps.last().map_or("unknown", String::as_str));
     }
 }
 
@@ -390,3 +1298,419 @@ fn analyze_generic_log_stats(lines: &[&str]) {
     println!("  Warning lines: {}", warning_lines);
     println!("  Average words per line: {:.1}", word_count as f64 / lines.len() as f64);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    /// A scratch path in the temp dir, unique to this process and test
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("log-analyzer-{}-{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+    
+    #[test]
+    fn test_limit_stops_after_n_entries() {
+        let input = temp_path("limit.log");
+        let output = temp_path("limit.json");
+        let log: String = (0..100)
+            .map(|i| format!("10.0.0.{} - - [10/Oct/2024:13:55:{:02} +0000] \"GET /page/{} HTTP/1.1\" 200 512\n", i % 8, i % 60, i))
+            .collect();
+        fs::write(&input, log).unwrap();
+        
+        parse_access_logs(&input, &output, &AccessLogOptions { limit: Some(5), ..Default::default() }).unwrap();
+        let entries: Vec<AccessLogEntry> = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        fs::remove_file(&input).unwrap();
+        fs::remove_file(&output).unwrap();
+        
+        assert_eq!(entries.len(), 5);
+        assert_eq!(entries.last().unwrap().path, "/page/4");
+    }
+    
+    #[test]
+    fn test_sessions_split_on_long_gaps() {
+        let parser = access_log_regex();
+        let entries: Vec<AccessLogEntry> = [
+            ("10.0.0.1", "10/Oct/2024:10:00:00 +0000"),
+            ("10.0.0.2", "10/Oct/2024:10:01:00 +0000"),
+            ("10.0.0.1", "10/Oct/2024:10:05:00 +0000"),
+            ("10.0.0.1", "10/Oct/2024:12:00:00 +0000"),
+        ]
+        .iter()
+        .filter_map(|(ip, time)| parse_access_line(&format!(r#"{} - - [{}] "GET / HTTP/1.1" 200 10"#, ip, time), &parser))
+        .collect();
+        
+        let sessions = sessionize(&entries, parse_gap("30m").unwrap());
+        let summary: Vec<_> = sessions.iter().map(|s| (s.ip.as_str(), s.requests, s.duration_secs)).collect();
+        assert_eq!(summary, vec![("10.0.0.1", 2, 300), ("10.0.0.1", 1, 0), ("10.0.0.2", 1, 0)]);
+        assert_eq!(sessions[1].start, "2024-10-10T12:00:00+00:00");
+        
+        assert_eq!(parse_gap("90").unwrap(), TimeDelta::seconds(90));
+        assert!(parse_gap("soon").is_err());
+        assert!(parse_gap("-5m").is_err());
+        assert!(parse_gap("9999999999999999h").is_err());
+        assert!(parse_gap(&format!("{}s", u64::MAX)).is_err());
+    }
+    
+    #[test]
+    fn test_nginx_error_lines_are_structured() {
+        let line = "2024/01/02 15:04:05 [error] 12345#0: *67 open() \"/var/www/favicon.ico\" failed (2: No such file or directory)";
+        let entry = parse_error_log_line(line, &error_log_regex(ErrorFormat::Nginx)).unwrap();
+        
+        assert_eq!(entry.level.as_deref(), Some("error"));
+        assert_eq!(entry.timestamp, "2024/01/02 15:04:05");
+        assert_eq!((entry.pid, entry.connection_id), (Some(12345), Some(67)));
+        assert_eq!(entry.message, "open() \"/var/www/favicon.ico\" failed (2: No such file or directory)");
+        
+        let apache = "[Wed Oct 11 14:32:52.123456 2000] [core:crit] [pid 35708:tid 4328636416] [client 72.15.99.187:5000] AH00037: Symbolic link not allowed";
+        let entry = parse_error_log_line(apache, &error_log_regex(ErrorFormat::Apache)).unwrap();
+        assert_eq!((entry.level.as_deref(), entry.pid), (Some("crit"), Some(35708)));
+        assert_eq!(entry.message, "AH00037: Symbolic link not allowed");
+        
+        // Not an Nginx line, so extract_errors falls back to the generic patterns
+        assert!(parse_error_log_line("2024-01-02 15:04:05 ERROR boom", &error_log_regex(ErrorFormat::Nginx)).is_none());
+    }
+    
+    /// Builds a minimal IPv4 MaxMind DB that maps exactly one address to a country and city:
+    /// a 32-node search tree down that address's bits, then its record, then the metadata
+    #[cfg(feature = "geoip")]
+    fn tiny_mmdb(ip: std::net::Ipv4Addr, country: &str, city: &str) -> Vec<u8> {
+        fn string(out: &mut Vec<u8>, s: &str) {
+            out.push(0x40 | s.len() as u8);
+            out.extend_from_slice(s.as_bytes());
+        }
+        fn map(out: &mut Vec<u8>, pairs: u8) {
+            out.push(0xE0 | pairs);
+        }
+        fn uint16(out: &mut Vec<u8>, value: u16) {
+            out.push(0xA2);
+            out.extend_from_slice(&value.to_be_bytes());
+        }
+        
+        const NODES: u32 = 32;
+        let bits = u32::from(ip);
+        let mut db = Vec::new();
+        for depth in 0..NODES {
+            // An on-path record points at the next node, or past the tree into the data section
+            let next = if depth + 1 < NODES { depth + 1 } else { NODES + 16 };
+            let on_path_is_right = (bits >> (31 - depth)) & 1 == 1;
+            let (left, right) = if on_path_is_right { (NODES, next) } else { (next, NODES) };
+            db.extend_from_slice(&left.to_be_bytes()[1..]);
+            db.extend_from_slice(&right.to_be_bytes()[1..]);
+        }
+        db.extend_from_slice(&[0; 16]);
+        
+        map(&mut db, 2);
+        string(&mut db, "country");
+        map(&mut db, 1);
+        string(&mut db, "names");
+        map(&mut db, 1);
+        string(&mut db, "en");
+        string(&mut db, country);
+        string(&mut db, "city");
+        map(&mut db, 1);
+        string(&mut db, "names");
+        map(&mut db, 1);
+        string(&mut db, "en");
+        string(&mut db, city);
+        
+        db.extend_from_slice(b"\xAB\xCD\xEFMaxMind.com");
+        map(&mut db, 9);
+        string(&mut db, "binary_format_major_version");
+        uint16(&mut db, 2);
+        string(&mut db, "binary_format_minor_version");
+        uint16(&mut db, 0);
+        string(&mut db, "build_epoch");
+        db.extend_from_slice(&[0x08, 0x02]);
+        db.extend_from_slice(&0u64.to_be_bytes());
+        string(&mut db, "database_type");
+        string(&mut db, "Test-City");
+        string(&mut db, "description");
+        map(&mut db, 0);
+        string(&mut db, "ip_version");
+        uint16(&mut db, 4);
+        string(&mut db, "languages");
+        db.extend_from_slice(&[0x00, 0x04]);
+        string(&mut db, "node_count");
+        db.push(0xC4);
+        db.extend_from_slice(&NODES.to_be_bytes());
+        string(&mut db, "record_size");
+        uint16(&mut db, 24);
+        db
+    }
+    
+    #[cfg(feature = "geoip")]
+    #[test]
+    fn test_geoip_annotates_known_ips() {
+        let reader = maxminddb::Reader::from_source(tiny_mmdb([81, 2, 69, 142].into(), "Sweden", "Stockholm")).unwrap();
+        let parser = access_log_regex();
+        let mut entries: Vec<AccessLogEntry> = ["81.2.69.142", "10.0.0.1", "not-an-ip"]
+            .iter()
+            .filter_map(|ip| parse_access_line(&format!(r#"{} - - [10/Oct/2024:13:55:01 +0000] "GET / HTTP/1.1" 200 10"#, ip), &parser))
+            .collect();
+        
+        let countries: Vec<String> = entries.iter_mut().map(|entry| geoip_lookup(entry, &reader)).collect();
+        
+        assert_eq!(entries[0].country.as_deref(), Some("Sweden"));
+        assert_eq!(entries[0].city.as_deref(), Some("Stockholm"));
+        assert_eq!(entries[1].country.as_deref(), Some("Unknown"));
+        assert_eq!(countries, ["Sweden", "Unknown", "Unknown"]);
+    }
+    
+    /// Parses one JSON log line and normalizes its timestamp
+    fn normalized(line: &str, format: &str) -> JsonLogEntry {
+        let mut entry: JsonLogEntry = serde_json::from_str(line).unwrap();
+        normalize_timestamp(&mut entry, &parse_time_format(format).unwrap());
+        entry
+    }
+    
+    #[test]
+    fn test_epoch_millis_timestamps_normalize() {
+        let entry = normalized(r#"{"timestamp": "1704207845123", "level": "info", "message": "up"}"#, "epoch_ms");
+        assert_eq!(entry.timestamp, "2024-01-02T15:04:05.123Z");
+        assert!(!entry.timestamp_unparsed);
+        
+        let garbled = normalized(r#"{"timestamp": "yesterday", "level": "info", "message": "up"}"#, "epoch_ms");
+        assert!(garbled.timestamp_unparsed);
+        assert_eq!(garbled.timestamp_raw.as_deref(), Some("yesterday"));
+    }
+    
+    #[test]
+    fn test_rfc3339_timestamps_normalize_to_utc() {
+        let entry = normalized(r#"{"timestamp": "2024-01-02T17:04:05+02:00", "level": "warn", "message": "hot"}"#, "rfc3339");
+        assert_eq!(entry.timestamp, "2024-01-02T15:04:05.000Z");
+        
+        let custom = normalized(r#"{"timestamp": "02/01/2024 15:04:05", "level": "warn", "message": "hot"}"#, "%d/%m/%Y %H:%M:%S");
+        assert_eq!(custom.timestamp, "2024-01-02T15:04:05.000Z");
+        assert!(parse_time_format("iso").is_err());
+    }
+    
+    #[test]
+    fn test_slow_requests_are_flagged() {
+        let lines = [
+            r#"10.0.0.1 - - [10/Oct/2024:13:55:01 +0000] "GET / HTTP/1.1" 200 512 "-" "curl/8.0" 0.012"#,
+            r#"10.0.0.2 - - [10/Oct/2024:13:55:02 +0000] "GET /report HTTP/1.1" 200 9000 "-" "curl/8.0" 2.350"#,
+            r#"10.0.0.3 - - [10/Oct/2024:13:55:03 +0000] "POST /login HTTP/1.1" 302 0 "-" "curl/8.0" 0.080"#,
+            r#"10.0.0.4 - - [10/Oct/2024:13:55:04 +0000] "GET /untimed HTTP/1.1" 200 128"#,
+        ];
+        let options = SlowRequestOptions { threshold_ms: 500.0, column: None, unit: DurationUnit::Seconds, top: 5 };
+        
+        let report = find_slow_requests(&lines, &options);
+        assert_eq!(report.timed, 3);
+        assert_eq!(report.slow, 1);
+        assert_eq!(report.slowest, vec![SlowRequest { path: "/report".to_string(), duration_ms: 2350.0 }]);
+    }
+    
+    #[test]
+    fn test_byte_totals_sum_responses() {
+        let lines = [
+            r#"10.0.0.1 - - [10/Oct/2024:13:55:01 +0000] "GET /video HTTP/1.1" 200 1048576"#,
+            r#"10.0.0.1 - - [10/Oct/2024:13:55:02 +0000] "GET /video HTTP/1.1" 206 524288"#,
+            r#"10.0.0.2 - - [10/Oct/2024:13:55:03 +0000] "GET / HTTP/1.1" 200 2048"#,
+            r#"10.0.0.3 - - [10/Oct/2024:13:55:04 +0000] "GET /missing HTTP/1.1" 404 512"#,
+            r#"10.0.0.3 - - [10/Oct/2024:13:55:05 +0000] "HEAD / HTTP/1.1" 304 -"#,
+        ];
+        let parser = access_log_regex();
+        let entries: Vec<AccessLogEntry> = lines.iter().filter_map(|line| parse_access_line(line, &parser)).collect();
+        
+        let totals = byte_totals(&entries);
+        assert_eq!(totals.total, 1_048_576 + 524_288 + 2048 + 512);
+        assert_eq!(totals.by_status_class["2xx"], 1_048_576 + 524_288 + 2048);
+        assert_eq!(totals.by_status_class["3xx"], 0);
+        assert_eq!(totals.by_path[0], ("/video".to_string(), 1_572_864));
+        
+        assert_eq!(format_bytes(totals.total), "1.5 MiB");
+        assert_eq!(format_bytes(512), "512 B");
+    }
+    
+    #[test]
+    fn test_markdown_report_has_tables() {
+        let lines = [
+            r#"10.0.0.1 - - [10/Oct/2024:13:55:01 +0000] "GET / HTTP/1.1" 200 512"#,
+            r#"10.0.0.1 - - [10/Oct/2024:13:57:30 +0000] "GET /missing HTTP/1.1" 404 128"#,
+            r#"10.0.0.2 - - [10/Oct/2024:13:56:00 +0000] "POST /api HTTP/1.1" 500 64"#,
+        ];
+        let stats = compute_access_stats(&lines, &StatsOptions::default());
+        let markdown = access_stats_markdown("access.log", lines.len(), 300, &stats);
+        
+        assert!(markdown.contains("- **Total lines:** 3\n"), "{}", markdown);
+        assert!(markdown.contains("| IP | Requests |\n|---|---:|\n| 10.0.0.1 | 2 |\n"), "{}", markdown);
+        assert!(markdown.contains("| Server errors (5xx) | 1 |"), "{}", markdown);
+        assert!(markdown.contains("2024-10-10T13:55:01+00:00 to 2024-10-10T13:57:30+00:00"), "{}", markdown);
+    }
+    
+    #[test]
+    fn test_unknown_methods_are_bucketed_as_other() {
+        let lines = [
+            r#"10.0.0.1 - - [10/Oct/2024:13:55:01 +0000] "GET / HTTP/1.1" 200 512"#,
+            r#"10.0.0.1 - - [10/Oct/2024:13:55:02 +0000] "PATCH /item HTTP/1.1" 204 0"#,
+            r#"10.0.0.2 - - [10/Oct/2024:13:55:03 +0000] "\x16\x03\x01 / HTTP/1.1" 400 0"#,
+        ];
+        let stats = compute_access_stats(&lines, &StatsOptions::default());
+        
+        assert_eq!(stats.methods.get("OTHER"), Some(&1));
+        assert_eq!(stats.methods.get("PATCH"), Some(&1));
+        assert!(!stats.methods.contains_key("\\x16\\x03\\x01"));
+        assert!(stats.method_format_warning().is_some());
+    }
+    
+    #[test]
+    fn test_bandwidth_lands_in_the_right_hour() {
+        let lines = [
+            r#"10.0.0.1 - - [10/Oct/2024:09:15:00 +0000] "GET /a HTTP/1.1" 200 1000"#,
+            r#"10.0.0.1 - - [10/Oct/2024:09:59:59 +0000] "GET /b HTTP/1.1" 200 500"#,
+            r#"10.0.0.2 - - [11/Oct/2024:17:00:00 +0000] "GET /c HTTP/1.1" 200 250"#,
+            r#"10.0.0.2 - - [11/Oct/2024:17:30:00 +0000] "GET /d HTTP/1.1" 304 -"#,
+        ];
+        let options = StatsOptions { bandwidth_by_hour: true, ..Default::default() };
+        let hours = compute_access_stats(&lines, &options).bandwidth_by_hour.unwrap();
+        
+        assert_eq!(hours[9], 1500);
+        assert_eq!(hours[17], 250);
+        assert_eq!(hours.iter().sum::<u64>(), 1750);
+        assert_eq!(hours.iter().filter(|bytes| **bytes == 0).count(), 22);
+    }
+    
+    #[test]
+    fn test_path_filter_keeps_only_matching_requests() {
+        let lines = [
+            r#"10.0.0.1 - - [10/Oct/2024:13:55:01 +0000] "GET /api/users HTTP/1.1" 200 100"#,
+            r#"10.0.0.1 - - [10/Oct/2024:13:55:02 +0000] "GET /index.html HTTP/1.1" 200 5000"#,
+            r#"10.0.0.2 - - [10/Oct/2024:13:55:03 +0000] "POST /api/login HTTP/1.1" 401 20"#,
+            r#"10.0.0.3 - - [10/Oct/2024:13:55:04 +0000] "GET /static/api/logo.png HTTP/1.1" 200 9000"#,
+        ];
+        let options = StatsOptions { path_filter: Some(Regex::new("^/api/.*").unwrap()), ..Default::default() };
+        let stats = compute_access_stats(&lines, &options);
+        
+        assert_eq!(stats.bytes.total, 120);
+        assert_eq!(stats.methods.values().sum::<usize>(), 2);
+        assert!(stats.bytes.by_path.iter().all(|(path, _)| path.starts_with("/api/")));
+        assert_eq!(stats.client_errors, 1);
+    }
+    
+    #[test]
+    fn test_bots_are_bucketed_separately() {
+        let googlebot = "Mozilla/5.0 (compatible; Googlebot/2.1; +http://www.google.com/bot.html)";
+        let chrome = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
+        
+        assert_eq!(agent_family(googlebot), "bot");
+        assert_eq!(agent_family("curl/8.4.0"), "bot");
+        assert_eq!(agent_family(chrome), "Chrome");
+        assert_eq!(agent_family("Lynx/2.8.9rel.1 libwww-FM/2.14"), "Lynx");
+        
+        let parser = access_log_regex();
+        let lines = [
+            format!(r#"66.249.66.1 - - [10/Oct/2024:13:55:01 +0000] "GET / HTTP/1.1" 200 512 "-" "{}""#, googlebot),
+            format!(r#"10.0.0.2 - - [10/Oct/2024:13:55:02 +0000] "GET / HTTP/1.1" 200 512 "-" "{}""#, chrome),
+        ];
+        let entries: Vec<AccessLogEntry> = lines.iter().filter_map(|line| parse_access_line(line, &parser)).collect();
+        
+        let agents = agent_breakdown(&entries);
+        assert_eq!((agents.humans, agents.bots), (1, 1));
+        assert_eq!(agents.families["Chrome"], 1);
+    }
+    
+    #[test]
+    fn test_files_larger_than_the_read_buffer_stream_through() {
+        let input = temp_path("large.log");
+        let output = temp_path("large.json");
+        let log: String = (0..5000)
+            .map(|i| format!("10.0.{}.{} - - [10/Oct/2024:13:55:{:02} +0000] \"GET /page/{} HTTP/1.1\" 200 512\n", i / 256, i % 256, i % 60, i))
+            .collect();
+        assert!(log.len() > 4 * READ_BUFFER_BYTES);
+        fs::write(&input, log).unwrap();
+        
+        parse_access_logs(&input, &output, &AccessLogOptions::default()).unwrap();
+        let entries: Vec<AccessLogEntry> = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        fs::remove_file(&input).unwrap();
+        fs::remove_file(&output).unwrap();
+        
+        assert_eq!(entries.len(), 5000);
+        assert_eq!(entries[4999].path, "/page/4999");
+        
+        let mut empty = Vec::new();
+        JsonArrayWriter::new(&mut empty).unwrap().finish().unwrap();
+        assert_eq!(serde_json::from_slice::<Vec<AccessLogEntry>>(&empty).unwrap().len(), 0);
+    }
+    
+    #[test]
+    fn test_gzipped_logs_parse_like_plain_ones() {
+        use flate2::{write::GzEncoder, Compression};
+        
+        let log: String = (0..40)
+            .map(|i| format!("10.0.0.{} - - [10/Oct/2024:13:55:{:02} +0000] \"GET /page/{} HTTP/1.1\" 200 512\n", i % 8, i, i))
+            .collect();
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(log.as_bytes()).unwrap();
+        let gzipped = encoder.finish().unwrap();
+        
+        // The second compressed copy has no .gz suffix, so only the magic bytes give it away
+        let plain = temp_path("plain.log");
+        let named = temp_path("named.log.gz");
+        let unnamed = temp_path("unnamed.log");
+        fs::write(&plain, &log).unwrap();
+        fs::write(&named, &gzipped).unwrap();
+        fs::write(&unnamed, &gzipped).unwrap();
+        
+        let counts: Vec<usize> = [&plain, &named, &unnamed]
+            .iter()
+            .map(|input| {
+                let output = temp_path("gzip.json");
+                parse_access_logs(input, &output, &AccessLogOptions::default()).unwrap();
+                let entries: Vec<AccessLogEntry> = serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+                fs::remove_file(&output).unwrap();
+                fs::remove_file(input).unwrap();
+                entries.len()
+            })
+            .collect();
+        
+        assert_eq!(counts, vec![40, 40, 40]);
+    }
+    
+    #[test]
+    fn test_clf_timestamps_parse_and_sort() {
+        let parser = access_log_regex();
+        let mut entries: Vec<AccessLogEntry> = [
+            "10.0.0.1 - - [10/Oct/2000:13:55:36 -0700] \"GET /late HTTP/1.1\" 200 1",
+            "10.0.0.2 - - [sometime last week] \"GET /unknown HTTP/1.1\" 200 1",
+            "10.0.0.3 - - [10/Oct/2000:22:00:00 +0200] \"GET /early HTTP/1.1\" 200 1",
+        ]
+        .iter()
+        .map(|line| parse_access_line(line, &parser).unwrap())
+        .collect();
+        
+        let json = serde_json::to_value(&entries[0]).unwrap();
+        assert_eq!(json["timestamp"], "2000-10-10T13:55:36-07:00");
+        assert!(json.get("timestamp_raw").is_none());
+        assert_eq!(entries[1].timestamp, None);
+        assert_eq!(entries[1].timestamp_raw.as_deref(), Some("sometime last week"));
+        
+        // 22:00 at +02:00 is 20:00 UTC, well before 13:55 at -07:00 (20:55 UTC)
+        sort_by_time(&mut entries);
+        let paths: Vec<&str> = entries.iter().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(paths, ["/early", "/late", "/unknown"]);
+    }
+    
+    #[test]
+    fn test_top_n_ranks_by_count_then_key() {
+        let requests = [
+            ("10.0.0.9", "/a", 200), ("10.0.0.9", "/a", 404), ("10.0.0.9", "/c", 200), ("10.0.0.5", "/b", 500),
+            ("10.0.0.5", "/c", 404), ("10.0.0.1", "/a", 200), ("10.0.0.1", "/b", 500),
+        ];
+        let log: Vec<String> = requests
+            .iter()
+            .map(|(ip, path, status)| format!("{} - - [10/Oct/2024:13:55:36 +0000] \"GET {} HTTP/1.1\" {} 1", ip, path, status))
+            .collect();
+        let lines: Vec<&str> = log.iter().map(String::as_str).collect();
+        
+        let stats = compute_access_stats(&lines, &StatsOptions { top: Some(2), ..Default::default() });
+        
+        // Second place is a tie each time (10.0.0.1/10.0.0.5, /b//c, 404/500), settled by key
+        assert_eq!(stats.top_ips, [("10.0.0.9".to_string(), 3), ("10.0.0.1".to_string(), 2)]);
+        assert_eq!(stats.top_paths, [("/a".to_string(), 3), ("/b".to_string(), 2)]);
+        let statuses: Vec<&str> = stats.status_codes.iter().map(|(status, _)| status.as_str()).collect();
+        assert_eq!(statuses, ["200", "404", "500"]);
+    }
+}
@@ -1,7 +1,19 @@
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use rayon::prelude::*;
+use rand::distributions::uniform::SampleUniform;
 use rand::prelude::*;
-use std::time::Instant;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::error::Error;
+use std::fmt::{self, Debug};
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Range, Sub};
+use std::path::Path;
+use std::time::{Duration, Instant};
 
 #[derive(Parser)]
 #[command(name = "matrix-multiplier")]
@@ -9,6 +21,10 @@ use std::time::Instant;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    
+    /// Rayon worker threads for the parallel algorithms (0 = all cores)
+    #[arg(long, global = true, default_value = "0")]
+    threads: usize,
 }
 
 #[derive(Subcommand)]
@@ -19,8 +35,32 @@ enum Commands {
         #[arg(default_value = "512")]
         size: usize,
         /// Number of iterations for timing
-        #[arg(long, default_value = "3")]
+        #[arg(long, default_value = "3", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
         iterations: usize,
+        /// Runs before the measured iterations whose timings are discarded
+        #[arg(long, default_value = "1")]
+        warmup: usize,
+        /// Matrix element type
+        #[arg(long, value_enum, default_value = "f64")]
+        dtype: Dtype,
+        #[command(flatten)]
+        input: InputOptions,
+        /// Report format: pretty text, or one JSON/CSV record per algorithm
+        #[arg(long, value_enum, default_value = "text")]
+        format: OutputFormat,
+        /// Largest element difference from the naive result that still passes, relative to
+        /// the naive product's largest magnitude, or absolute below 1 (default: 1e-6 for f64, 1e-3 for f32)
+        #[arg(long)]
+        tolerance: Option<f64>,
+        /// Size at or below which Strassen and Winograd switch to naive multiplication
+        #[arg(long, default_value_t = STRASSEN_THRESHOLD)]
+        strassen_threshold: usize,
+        /// Tile edge for the blocked algorithms, or "auto" to size tiles for the L2 cache
+        #[arg(long, default_value = "64", value_parser = parse_block_size)]
+        block_size: BlockSize,
+        /// Treat NaNs in the same position as equal when verifying results
+        #[arg(long)]
+        nan_equal: bool,
     },
     /// Compare algorithm complexities across sizes
     Scaling {
@@ -33,83 +73,624 @@ enum Commands {
         /// Size multiplier for each step
         #[arg(long, default_value = "2")]
         factor: usize,
+        /// Matrix element type
+        #[arg(long, value_enum, default_value = "f64")]
+        dtype: Dtype,
+        #[command(flatten)]
+        input: InputOptions,
+        /// Also write the table as CSV (size,naive_s,parallel_s,blocked_s,speedup)
+        #[arg(long)]
+        out: Option<String>,
+    },
+    /// Benchmark scalar vs parallel matrix-vector multiplication
+    Matvec {
+        /// Matrix size (NxN)
+        #[arg(default_value = "4096")]
+        size: usize,
+        /// Number of iterations for timing
+        #[arg(long, default_value = "10", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        iterations: usize,
+        /// Matrix element type
+        #[arg(long, value_enum, default_value = "f64")]
+        dtype: Dtype,
+        #[command(flatten)]
+        input: InputOptions,
+    },
+    /// Strong scaling: a fixed size timed across thread counts
+    Threads {
+        /// Matrix size
+        #[arg(default_value = "512")]
+        size: usize,
+        /// Largest thread count in the sweep (default: the --threads pool size)
+        #[arg(long)]
+        max_threads: Option<usize>,
+        /// Matrix element type
+        #[arg(long, value_enum, default_value = "f64")]
+        dtype: Dtype,
+        #[command(flatten)]
+        input: InputOptions,
     },
     /// Demonstrate different optimization techniques
     Techniques {
         /// Matrix size for demonstration
         #[arg(default_value = "256")]
         size: usize,
+        /// Matrix element type
+        #[arg(long, value_enum, default_value = "f64")]
+        dtype: Dtype,
+        #[command(flatten)]
+        input: InputOptions,
     },
     /// Memory access pattern analysis
     Memory {
         /// Matrix size
         #[arg(default_value = "512")]
         size: usize,
+        /// Matrix element type
+        #[arg(long, value_enum, default_value = "f64")]
+        dtype: Dtype,
+        #[command(flatten)]
+        input: InputOptions,
+    },
+    /// Solve a random Ax = b by LU decomposition and check the residual
+    Solve {
+        /// System size (NxN)
+        #[arg(default_value = "256")]
+        size: usize,
+        /// Matrix element type
+        #[arg(long, value_enum, default_value = "f64")]
+        dtype: Dtype,
+        #[command(flatten)]
+        input: InputOptions,
+    },
+    /// Multiply two matrices loaded from .csv or .json files
+    Multiply {
+        /// Left operand
+        a: String,
+        /// Right operand
+        b: String,
+        /// Where to write the product (.csv or .json)
+        out: String,
+        /// naive, parallel, transposed, blocked, parallel-blocked, strassen, parallel-strassen, or winograd
+        #[arg(long, default_value = "parallel-blocked")]
+        algorithm: String,
     },
 }
 
+/// Element type selected with --dtype
+#[derive(Clone, Copy, ValueEnum)]
+enum Dtype {
+    F32,
+    F64,
+}
+
+/// Numeric element a `Matrix` can hold
+trait Element:
+    Copy
+    + Debug
+    + PartialOrd
+    + Send
+    + Sync
+    + Sum
+    + SampleUniform
+    + Serialize
+    + DeserializeOwned
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + AddAssign
+{
+    /// Name shown in benchmark headers
+    const NAME: &'static str;
+    /// Default tolerance when comparing against the naive result
+    const TOLERANCE: f64;
+    /// Machine epsilon, used to spot singular pivots
+    const EPSILON: f64;
+
+    fn zero() -> Self;
+    fn one() -> Self;
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_element {
+    ($t:ty, $tolerance:expr) => {
+        impl Element for $t {
+            const NAME: &'static str = stringify!($t);
+            const TOLERANCE: f64 = $tolerance;
+            const EPSILON: f64 = <$t>::EPSILON as f64;
+
+            fn zero() -> Self {
+                0.0
+            }
+
+            fn one() -> Self {
+                1.0
+            }
+
+            fn from_f64(value: f64) -> Self {
+                value as $t
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        }
+    };
+}
+
+impl_element!(f32, 1e-3);
+impl_element!(f64, 1e-6);
+
+/// How the benchmark subcommands generate their random operands
+#[derive(Args, Clone, Copy, Debug)]
+struct InputOptions {
+    /// Seed for the left operand; the right one uses seed + 42
+    #[arg(long, default_value = "42")]
+    seed: u64,
+    /// Interval the random elements are drawn from, as min:max
+    #[arg(long, default_value = "-1:1", value_parser = parse_value_range, allow_hyphen_values = true)]
+    value_range: (f64, f64),
+}
+
+impl InputOptions {
+    /// The value range converted to `T`, which can overflow or collapse bounds that were
+    /// fine as f64, e.g. 1e39 or 0:1e-50 in f32
+    fn range<T: Element>(&self) -> Result<Range<T>, MatrixError> {
+        let (min, max) = (T::from_f64(self.value_range.0), T::from_f64(self.value_range.1));
+        let finite = |value: T| value.to_f64().is_finite();
+        if !finite(min) || !finite(max) || min >= max || !finite(max - min) {
+            return Err(MatrixError::InvalidData(format!(
+                "value range {:?}:{:?} doesn't fit {} (becomes {:?}:{:?})",
+                self.value_range.0, self.value_range.1, T::NAME, min, max
+            )));
+        }
+        Ok(min..max)
+    }
+    
+    /// The A and B operands for an n×n benchmark
+    fn operands<T: Element>(&self, size: usize) -> Result<(Matrix<T>, Matrix<T>), MatrixError> {
+        Ok((
+            Matrix::random(size, size, self.seed, self.range()?),
+            Matrix::random(size, size, self.seed.wrapping_add(42), self.range()?),
+        ))
+    }
+}
+
+fn parse_value_range(value: &str) -> Result<(f64, f64), String> {
+    let (min, max) = value
+        .split_once(':')
+        .ok_or_else(|| format!("expected min:max, got '{}'", value))?;
+    let parse = |bound: &str| bound.trim().parse::<f64>().map_err(|e| format!("'{}': {}", bound, e));
+    let (min, max) = (parse(min)?, parse(max)?);
+    
+    // The sampler needs the width as well as both bounds to be finite
+    if !min.is_finite() || !max.is_finite() || min >= max || !(max - min).is_finite() {
+        return Err(format!("value range {:?}:{:?} must be finite, with min < max and a finite max - min", min, max));
+    }
+    Ok((min, max))
+}
+
+/// L, U and the row permutation produced by `Matrix::lu_decompose`
+type LuFactors<T> = (Matrix<T>, Matrix<T>, Vec<usize>);
+
 #[derive(Debug)]
-struct Matrix {
-    data: Vec<f64>,
+struct Matrix<T: Element> {
+    data: Vec<T>,
     rows: usize,
     cols: usize,
 }
 
-impl Matrix {
+impl<T: Element> Matrix<T> {
     fn new(rows: usize, cols: usize) -> Self {
         Matrix {
-            data: vec![0.0; rows * cols],
+            data: vec![T::zero(); rows * cols],
             rows,
             cols,
         }
     }
 
-    fn random(rows: usize, cols: usize, seed: u64) -> Self {
+    fn random(rows: usize, cols: usize, seed: u64, range: Range<T>) -> Self {
         let mut rng = StdRng::seed_from_u64(seed);
         Matrix {
-            data: (0..rows * cols).map(|_| rng.gen_range(-1.0..1.0)).collect(),
+            data: (0..rows * cols).map(|_| rng.gen_range(range.clone())).collect(),
             rows,
             cols,
         }
     }
 
-    fn get(&self, row: usize, col: usize) -> f64 {
+    fn get(&self, row: usize, col: usize) -> T {
         self.data[row * self.cols + col]
     }
 
-    fn set(&mut self, row: usize, col: usize, value: f64) {
+    fn set(&mut self, row: usize, col: usize, value: T) {
         self.data[row * self.cols + col] = value;
     }
 
-    fn verify_equal(&self, other: &Matrix, tolerance: f64) -> bool {
-        if self.rows != other.rows || self.cols != other.cols {
+    fn identity(n: usize) -> Self {
+        Matrix::from_fn(n, n, |i, j| if i == j { T::one() } else { T::zero() })
+    }
+
+    fn from_fn(rows: usize, cols: usize, f: impl Fn(usize, usize) -> T) -> Self {
+        Matrix {
+            data: (0..rows * cols).map(|index| f(index / cols, index % cols)).collect(),
+            rows,
+            cols,
+        }
+    }
+
+    /// Wraps row-major `data`, which must hold exactly rows × cols elements
+    fn from_vec(rows: usize, cols: usize, data: Vec<T>) -> Result<Self, MatrixError> {
+        if data.len() != rows * cols {
+            return Err(MatrixError::DimensionMismatch(format!(
+                "{} values cannot fill a {}×{} matrix",
+                data.len(), rows, cols
+            )));
+        }
+        Ok(Matrix { data, rows, cols })
+    }
+
+    fn transpose(&self) -> Matrix<T> {
+        Matrix::from_fn(self.cols, self.rows, |i, j| self.get(j, i))
+    }
+
+    /// Matrix-vector product `self · v`
+    fn multiply_vec(&self, v: &[T]) -> Result<Vec<T>, MatrixError> {
+        self.check_vec_len(v)?;
+        Ok(self.row_slices().map(|row| dot(row, v)).collect())
+    }
+
+    /// Matrix-vector product with rows spread across the rayon pool
+    fn multiply_vec_parallel(&self, v: &[T]) -> Result<Vec<T>, MatrixError> {
+        self.check_vec_len(v)?;
+        Ok(self.data.par_chunks(self.cols.max(1)).map(|row| dot(row, v)).collect())
+    }
+
+    fn check_vec_len(&self, v: &[T]) -> Result<(), MatrixError> {
+        if v.len() != self.cols {
+            return Err(MatrixError::DimensionMismatch(format!(
+                "cannot multiply {}×{} by a vector of length {}",
+                self.rows, self.cols, v.len()
+            )));
+        }
+        Ok(())
+    }
+
+    fn row_slices(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks(self.cols.max(1))
+    }
+
+    /// Copies the matrix into the top-left corner of an n×n zero matrix
+    fn padded(&self, n: usize) -> Matrix<T> {
+        let mut result = Matrix::new(n, n);
+        for i in 0..self.rows {
+            result.data[i * n..i * n + self.cols].copy_from_slice(&self.data[i * self.cols..(i + 1) * self.cols]);
+        }
+        result
+    }
+
+    /// Keeps only the top-left rows×cols corner
+    fn trimmed(&self, rows: usize, cols: usize) -> Matrix<T> {
+        let mut result = Matrix::new(rows, cols);
+        for i in 0..rows {
+            result.data[i * cols..(i + 1) * cols].copy_from_slice(&self.data[i * self.cols..i * self.cols + cols]);
+        }
+        result
+    }
+
+    /// True when every element is within `tolerance` of `reference`'s, scaled by the
+    /// reference's largest finite magnitude (when above 1) since rounding error grows with it
+    fn verify_equal(&self, reference: &Matrix<T>, tolerance: f64) -> bool {
+        self.verify_equal_with(reference, tolerance, false)
+    }
+
+    /// Like `verify_equal`, but with `nan_equal` two NaNs in the same
+    /// position count as a match instead of a mismatch
+    fn verify_equal_with(&self, reference: &Matrix<T>, tolerance: f64, nan_equal: bool) -> bool {
+        if self.rows != reference.rows || self.cols != reference.cols {
             return false;
         }
         
-        for i in 0..self.data.len() {
-            if (self.data[i] - other.data[i]).abs() > tolerance {
-                return false;
+        let scale = reference.data.iter().map(|y| y.to_f64().abs()).filter(|y| y.is_finite()).fold(1.0, f64::max);
+        let tolerance = tolerance * scale;
+        self.data.iter().zip(&reference.data).all(|(&x, &y)| {
+            let (x, y) = (x.to_f64(), y.to_f64());
+            // Equal infinities differ by NaN, so compare them directly
+            x == y || (x - y).abs() <= tolerance || (nan_equal && x.is_nan() && y.is_nan())
+        })
+    }
+
+    /// Sum of the diagonal
+    fn trace(&self) -> f64 {
+        assert_eq!(self.rows, self.cols, "Trace requires a square matrix");
+        (0..self.rows).map(|i| self.get(i, i).to_f64()).sum()
+    }
+
+    /// Square root of the sum of squared elements
+    fn frobenius_norm(&self) -> f64 {
+        self.data.iter().map(|x| x.to_f64().powi(2)).sum::<f64>().sqrt()
+    }
+
+    /// Largest element magnitude
+    fn max_abs(&self) -> f64 {
+        self.data.iter().map(|x| x.to_f64().abs()).fold(0.0, f64::max)
+    }
+
+    /// True when any element is NaN or infinite, e.g. after an overflow
+    fn has_non_finite(&self) -> bool {
+        self.data.iter().any(|value| !value.to_f64().is_finite())
+    }
+
+    /// LU decomposition with partial pivoting: returns L, U and `perm` such that
+    /// row i of L·U is row `perm[i]` of `self`
+    fn lu_decompose(&self) -> Result<LuFactors<T>, MatrixError> {
+        if self.rows != self.cols {
+            return Err(MatrixError::DimensionMismatch(format!(
+                "LU decomposition needs a square matrix, got {}×{}",
+                self.rows, self.cols
+            )));
+        }
+        
+        let n = self.rows;
+        let mut lower = Matrix::identity(n);
+        let mut upper = Matrix { data: self.data.clone(), rows: n, cols: n };
+        let mut perm: Vec<usize> = (0..n).collect();
+        // Pivots this small next to the largest element are rounding noise, not information
+        let singular_below = self.max_abs() * n as f64 * T::EPSILON;
+        
+        for k in 0..n {
+            // Partial pivoting: bring the largest remaining entry of column k onto the diagonal
+            let pivot_row = (k..n)
+                .max_by(|&i, &j| upper.get(i, k).to_f64().abs().total_cmp(&upper.get(j, k).to_f64().abs()))
+                .unwrap_or(k);
+            let pivot = upper.get(pivot_row, k);
+            if pivot.to_f64().abs() <= singular_below {
+                return Err(MatrixError::ComputationError(format!(
+                    "matrix is singular (no usable pivot in column {})",
+                    k + 1
+                )));
+            }
+            
+            if pivot_row != k {
+                perm.swap(k, pivot_row);
+                for j in 0..n {
+                    upper.data.swap(k * n + j, pivot_row * n + j);
+                }
+                // Only the multipliers already stored in L move with the row
+                for j in 0..k {
+                    lower.data.swap(k * n + j, pivot_row * n + j);
+                }
+            }
+            
+            for i in k + 1..n {
+                let factor = upper.get(i, k) / pivot;
+                lower.set(i, k, factor);
+                upper.set(i, k, T::zero());
+                for j in k + 1..n {
+                    upper.set(i, j, upper.get(i, j) - factor * upper.get(k, j));
+                }
             }
         }
-        true
+        
+        Ok((lower, upper, perm))
+    }
+
+    /// Solves `self · x = b` through the LU factors: L·y = P·b, then U·x = y
+    fn solve(&self, b: &[T]) -> Result<Vec<T>, MatrixError> {
+        let (lower, upper, perm) = self.lu_decompose()?;
+        let n = self.rows;
+        if b.len() != n {
+            return Err(MatrixError::DimensionMismatch(format!(
+                "a {}×{} system needs a right-hand side of length {}, got {}",
+                n, n, n, b.len()
+            )));
+        }
+        
+        // L has a unit diagonal, so forward substitution needs no division
+        let mut y: Vec<T> = Vec::with_capacity(n);
+        for (i, &row) in perm.iter().enumerate() {
+            let known = dot(&lower.data[i * n..i * n + i], &y);
+            y.push(b[row] - known);
+        }
+        
+        let mut x = vec![T::zero(); n];
+        for i in (0..n).rev() {
+            let known = dot(&upper.data[i * n + i + 1..(i + 1) * n], &x[i + 1..]);
+            x[i] = (y[i] - known) / upper.get(i, i);
+        }
+        
+        Ok(x)
+    }
+
+    fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, MatrixError> {
+        let cols = rows.first().map_or(0, Vec::len);
+        if cols == 0 {
+            return Err(MatrixError::InvalidData("matrix has no elements".to_string()));
+        }
+        if let Some(i) = rows.iter().position(|row| row.len() != cols) {
+            return Err(MatrixError::InvalidData(format!(
+                "row {} has {} values, expected {}",
+                i + 1,
+                rows[i].len(),
+                cols
+            )));
+        }
+        
+        Matrix::from_vec(rows.len(), cols, rows.into_iter().flatten().collect())
+    }
+
+    fn to_rows(&self) -> Vec<Vec<T>> {
+        self.data.chunks(self.cols.max(1)).map(<[T]>::to_vec).collect()
+    }
+
+    /// Reads one matrix row per CSV record, without a header
+    fn from_csv(path: &str) -> Result<Self, MatrixError> {
+        let csv_error = |e: csv::Error| MatrixError::InvalidData(format!("{}: {}", path, e));
+        let rows = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_path(path)
+            .and_then(|mut reader| reader.deserialize().collect())
+            .map_err(csv_error)?;
+        Matrix::from_rows(rows)
+    }
+
+    fn to_csv(&self, path: &str) -> Result<(), MatrixError> {
+        let csv_error = |e: csv::Error| MatrixError::InvalidData(format!("{}: {}", path, e));
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(false)
+            .from_path(path)
+            .map_err(csv_error)?;
+        for row in self.to_rows() {
+            writer.serialize(row).map_err(csv_error)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Reads a JSON array of rows, e.g. `[[1, 2], [3, 4]]`
+    fn from_json(path: &str) -> Result<Self, MatrixError> {
+        let rows = serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| MatrixError::InvalidData(format!("{}: {}", path, e)))?;
+        Matrix::from_rows(rows)
+    }
+
+    fn to_json(&self, path: &str) -> Result<(), MatrixError> {
+        let json = serde_json::to_string(&self.to_rows())
+            .map_err(|e| MatrixError::InvalidData(format!("{}: {}", path, e)))?;
+        fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Loads a matrix, picking the format from the .csv or .json extension
+    fn load(path: &str) -> Result<Self, MatrixError> {
+        match extension(path)? {
+            FileFormat::Csv => Matrix::from_csv(path),
+            FileFormat::Json => Matrix::from_json(path),
+        }
+    }
+
+    fn save(&self, path: &str) -> Result<(), MatrixError> {
+        match extension(path)? {
+            FileFormat::Csv => self.to_csv(path),
+            FileFormat::Json => self.to_json(path),
+        }
+    }
+}
+
+#[derive(Debug)]
+enum MatrixError {
+    InvalidData(String),
+    DimensionMismatch(String),
+    ComputationError(String),
+    IoError(std::io::Error),
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::InvalidData(msg) => write!(f, "invalid data: {}", msg),
+            MatrixError::DimensionMismatch(msg) => write!(f, "dimension mismatch: {}", msg),
+            MatrixError::ComputationError(msg) => write!(f, "computation failed: {}", msg),
+            MatrixError::IoError(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl Error for MatrixError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MatrixError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for MatrixError {
+    fn from(error: std::io::Error) -> Self {
+        MatrixError::IoError(error)
+    }
+}
+
+enum FileFormat {
+    Csv,
+    Json,
+}
+
+fn extension(path: &str) -> Result<FileFormat, MatrixError> {
+    match Path::new(path).extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("csv") => Ok(FileFormat::Csv),
+        Some(ext) if ext.eq_ignore_ascii_case("json") => Ok(FileFormat::Json),
+        _ => Err(MatrixError::InvalidData(format!(
+            "{}: expected a .csv or .json extension",
+            path
+        ))),
     }
 }
 
+fn dot<T: Element>(x: &[T], y: &[T]) -> T {
+    x.iter().zip(y).map(|(&a, &b)| a * b).sum()
+}
+
+/// Checks that `a * b` is defined before handing the pair to an algorithm
+fn check_dimensions<T: Element>(a: &Matrix<T>, b: &Matrix<T>) -> Result<(), MatrixError> {
+    if a.cols != b.rows {
+        return Err(MatrixError::DimensionMismatch(format!(
+            "cannot multiply {}×{} by {}×{}",
+            a.rows, a.cols, b.rows, b.cols
+        )));
+    }
+    Ok(())
+}
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
+    
+    // num_threads(0) lets rayon pick, which means one worker per core
+    let pool = rayon::ThreadPoolBuilder::new().num_threads(cli.threads).build()?;
+    pool.install(|| run(cli.command).map_err(|e| e.to_string()))?;
+    
+    Ok(())
+}
 
-    match cli.command {
-        Commands::Benchmark { size, iterations } => {
-            benchmark_algorithms(size, iterations)?;
-        }
-        Commands::Scaling { start_size, end_size, factor } => {
-            analyze_scaling(start_size, end_size, factor)?;
+fn run(command: Commands) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        Commands::Benchmark { size, iterations, warmup, dtype, input, format, tolerance, strassen_threshold, block_size, nan_equal } => {
+            let options = BenchmarkOptions { iterations, warmup, format, tolerance, strassen_threshold, block_size, nan_equal };
+            match dtype {
+                Dtype::F32 => benchmark_algorithms::<f32>(size, &input, &options)?,
+                Dtype::F64 => benchmark_algorithms::<f64>(size, &input, &options)?,
+            }
         }
-        Commands::Techniques { size } => {
-            demonstrate_techniques(size)?;
+        Commands::Scaling { start_size, end_size, factor, dtype, input, out } => match dtype {
+            Dtype::F32 => analyze_scaling::<f32>(start_size, end_size, factor, &input, out.as_deref())?,
+            Dtype::F64 => analyze_scaling::<f64>(start_size, end_size, factor, &input, out.as_deref())?,
+        },
+        Commands::Techniques { size, dtype, input } => match dtype {
+            Dtype::F32 => demonstrate_techniques::<f32>(size, &input)?,
+            Dtype::F64 => demonstrate_techniques::<f64>(size, &input)?,
+        },
+        Commands::Memory { size, dtype, input } => match dtype {
+            Dtype::F32 => analyze_memory_patterns::<f32>(size, &input)?,
+            Dtype::F64 => analyze_memory_patterns::<f64>(size, &input)?,
+        },
+        Commands::Solve { size, dtype, input } => match dtype {
+            Dtype::F32 => solve_system::<f32>(size, &input)?,
+            Dtype::F64 => solve_system::<f64>(size, &input)?,
+        },
+        Commands::Multiply { a, b, out, algorithm } => {
+            multiply_files(&a, &b, &out, &algorithm)?;
         }
-        Commands::Memory { size } => {
-            analyze_memory_patterns(size)?;
+        Commands::Matvec { size, iterations, dtype, input } => match dtype {
+            Dtype::F32 => benchmark_matvec::<f32>(size, iterations, &input)?,
+            Dtype::F64 => benchmark_matvec::<f64>(size, iterations, &input)?,
+        },
+        Commands::Threads { size, max_threads, dtype, input } => {
+            let max_threads = max_threads.unwrap_or_else(rayon::current_num_threads);
+            match dtype {
+                Dtype::F32 => analyze_thread_scaling::<f32>(size, max_threads, &input)?,
+                Dtype::F64 => analyze_thread_scaling::<f64>(size, max_threads, &input)?,
+            }
         }
     }
 
@@ -117,14 +698,14 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 /// Naive O(n³) matrix multiplication - baseline implementation
-fn multiply_naive(a: &Matrix, b: &Matrix) -> Matrix {
+fn multiply_naive<T: Element>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
     assert_eq!(a.cols, b.rows, "Matrix dimensions don't match for multiplication");
     
     let mut result = Matrix::new(a.rows, b.cols);
     
     for i in 0..a.rows {
         for j in 0..b.cols {
-            let mut sum = 0.0;
+            let mut sum = T::zero();
             for k in 0..a.cols {
                 sum += a.get(i, k) * b.get(k, j);
             }
@@ -136,7 +717,7 @@ fn multiply_naive(a: &Matrix, b: &Matrix) -> Matrix {
 }
 
 /// Parallel naive multiplication using rayon
-fn multiply_parallel_naive(a: &Matrix, b: &Matrix) -> Matrix {
+fn multiply_parallel_naive<T: Element>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
     assert_eq!(a.cols, b.rows, "Matrix dimensions don't match for multiplication");
     
     let mut result = Matrix::new(a.rows, b.cols);
@@ -146,20 +727,137 @@ fn multiply_parallel_naive(a: &Matrix, b: &Matrix) -> Matrix {
         .par_chunks_mut(b.cols)
         .enumerate()
         .for_each(|(i, row_chunk)| {
-            for j in 0..b.cols {
-                let mut sum = 0.0;
+            for (j, cell) in row_chunk.iter_mut().enumerate() {
+                let mut sum = T::zero();
                 for k in 0..a.cols {
                     sum += a.get(i, k) * b.get(k, j);
                 }
-                row_chunk[j] = sum;
+                *cell = sum;
             }
         });
     
     result
 }
 
+/// Naive multiplication against a pre-transposed `b`, so the k loop walks
+/// both operands row-major
+fn multiply_transposed<T: Element>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+    assert_eq!(a.cols, b.rows, "Matrix dimensions don't match for multiplication");
+    
+    let bt = b.transpose();
+    let mut result = Matrix::new(a.rows, b.cols);
+    
+    for i in 0..a.rows {
+        let a_row = &a.data[i * a.cols..(i + 1) * a.cols];
+        for j in 0..b.cols {
+            let bt_row = &bt.data[j * bt.cols..(j + 1) * bt.cols];
+            let sum = a_row.iter().zip(bt_row).map(|(&x, &y)| x * y).sum();
+            result.set(i, j, sum);
+        }
+    }
+    
+    result
+}
+
+/// ikj loop order: the inner loop streams a row of `b` and a row of the
+/// result, both contiguous in row-major storage
+fn multiply_ikj<T: Element>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+    assert_eq!(a.cols, b.rows, "Matrix dimensions don't match for multiplication");
+    
+    let mut result = Matrix::new(a.rows, b.cols);
+    
+    for i in 0..a.rows {
+        for k in 0..a.cols {
+            let a_ik = a.get(i, k);
+            for j in 0..b.cols {
+                let sum = result.get(i, j) + a_ik * b.get(k, j);
+                result.set(i, j, sum);
+            }
+        }
+    }
+    
+    result
+}
+
+/// jik loop order: like ijk but walks the result column by column, so both
+/// `b` and the result are strided
+fn multiply_jik<T: Element>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+    assert_eq!(a.cols, b.rows, "Matrix dimensions don't match for multiplication");
+    
+    let mut result = Matrix::new(a.rows, b.cols);
+    
+    for j in 0..b.cols {
+        for i in 0..a.rows {
+            let mut sum = T::zero();
+            for k in 0..a.cols {
+                sum += a.get(i, k) * b.get(k, j);
+            }
+            result.set(i, j, sum);
+        }
+    }
+    
+    result
+}
+
+/// Edge length at which the cache-oblivious recursion stops splitting
+const RECURSIVE_BASE: usize = 32;
+
+/// Cache-oblivious divide-and-conquer multiplication
+///
+/// Halves the largest of the three dimensions until every piece fits the
+/// base block, so some level of the recursion matches each cache level
+/// without knowing its size.
+fn multiply_recursive<T: Element>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
+    assert_eq!(a.cols, b.rows, "Matrix dimensions don't match for multiplication");
+    
+    let mut result = Matrix::new(a.rows, b.cols);
+    recursive_accumulate(a, b, &mut result, 0..a.rows, 0..b.cols, 0..a.cols);
+    result
+}
+
+/// Adds a[rows, inner] · b[inner, cols] into result[rows, cols]
+fn recursive_accumulate<T: Element>(
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    result: &mut Matrix<T>,
+    rows: Range<usize>,
+    cols: Range<usize>,
+    inner: Range<usize>,
+) {
+    let (m, n, p) = (rows.len(), cols.len(), inner.len());
+    
+    if m.max(n).max(p) <= RECURSIVE_BASE {
+        for i in rows {
+            for k in inner.clone() {
+                let a_ik = a.get(i, k);
+                for j in cols.clone() {
+                    let sum = result.get(i, j) + a_ik * b.get(k, j);
+                    result.set(i, j, sum);
+                }
+            }
+        }
+        return;
+    }
+    
+    // Split the largest dimension at its midpoint; splitting the inner
+    // dimension means both halves accumulate into the same output block
+    if m >= n && m >= p {
+        let mid = rows.start + m / 2;
+        recursive_accumulate(a, b, result, rows.start..mid, cols.clone(), inner.clone());
+        recursive_accumulate(a, b, result, mid..rows.end, cols, inner);
+    } else if n >= p {
+        let mid = cols.start + n / 2;
+        recursive_accumulate(a, b, result, rows.clone(), cols.start..mid, inner.clone());
+        recursive_accumulate(a, b, result, rows, mid..cols.end, inner);
+    } else {
+        let mid = inner.start + p / 2;
+        recursive_accumulate(a, b, result, rows.clone(), cols.clone(), inner.start..mid);
+        recursive_accumulate(a, b, result, rows, cols, mid..inner.end);
+    }
+}
+
 /// Cache-optimized block multiplication
-fn multiply_blocked(a: &Matrix, b: &Matrix, block_size: usize) -> Matrix {
+fn multiply_blocked<T: Element>(a: &Matrix<T>, b: &Matrix<T>, block_size: usize) -> Matrix<T> {
     assert_eq!(a.cols, b.rows, "Matrix dimensions don't match for multiplication");
     
     let mut result = Matrix::new(a.rows, b.cols);
@@ -190,68 +888,73 @@ fn multiply_blocked(a: &Matrix, b: &Matrix, block_size: usize) -> Matrix {
 }
 
 /// Parallel blocked multiplication
-fn multiply_parallel_blocked(a: &Matrix, b: &Matrix, block_size: usize) -> Matrix {
+///
+/// Each task owns one band of `block_size` output rows, so no two threads
+/// ever write the same element.
+fn multiply_parallel_blocked<T: Element>(a: &Matrix<T>, b: &Matrix<T>, block_size: usize) -> Matrix<T> {
     assert_eq!(a.cols, b.rows, "Matrix dimensions don't match for multiplication");
     
     let mut result = Matrix::new(a.rows, b.cols);
+    let cols = b.cols;
     
-    // Create block ranges
-    let row_blocks: Vec<_> = (0..a.rows).step_by(block_size).collect();
-    let col_blocks: Vec<_> = (0..b.cols).step_by(block_size).collect();
-    let inner_blocks: Vec<_> = (0..a.cols).step_by(block_size).collect();
+    if cols == 0 {
+        return result;
+    }
     
-    // Parallel over block combinations
-    row_blocks.par_iter().for_each(|&ii| {
-        for &jj in &col_blocks {
-            let mut local_sum = vec![0.0; block_size * block_size];
+    // Parallel over row bands
+    result.data
+        .par_chunks_mut(block_size * cols)
+        .enumerate()
+        .for_each(|(band, band_chunk)| {
+            let ii = band * block_size;
+            let i_end = (ii + block_size).min(a.rows);
             
-            for &kk in &inner_blocks {
-                let i_end = (ii + block_size).min(a.rows);
-                let j_end = (jj + block_size).min(b.cols);
-                let k_end = (kk + block_size).min(a.cols);
+            for jj in (0..cols).step_by(block_size) {
+                let j_end = (jj + block_size).min(cols);
                 
-                for i in ii..i_end {
-                    for j in jj..j_end {
-                        for k in kk..k_end {
-                            local_sum[(i - ii) * block_size + (j - jj)] += 
-                                a.get(i, k) * b.get(k, j);
+                for kk in (0..a.cols).step_by(block_size) {
+                    let k_end = (kk + block_size).min(a.cols);
+                    
+                    for i in ii..i_end {
+                        for j in jj..j_end {
+                            let mut sum = band_chunk[(i - ii) * cols + j];
+                            for k in kk..k_end {
+                                sum += a.get(i, k) * b.get(k, j);
+                            }
+                            band_chunk[(i - ii) * cols + j] = sum;
                         }
                     }
                 }
             }
-            
-            // Write back results (needs synchronization in real implementation)
-            let i_end = (ii + block_size).min(a.rows);
-            let j_end = (jj + block_size).min(b.cols);
-            for i in ii..i_end {
-                for j in jj..j_end {
-                    unsafe {
-                        let ptr = result.data.as_ptr() as *mut f64;
-                        *ptr.add(i * result.cols + j) = local_sum[(i - ii) * block_size + (j - jj)];
-                    }
-                }
-            }
-        }
-    });
+        });
     
     result
 }
 
+/// Default size at or below which Strassen hands off to the naive kernel
+const STRASSEN_THRESHOLD: usize = 64;
+
 /// Strassen's algorithm (recursive, O(n^2.807))
-fn multiply_strassen(a: &Matrix, b: &Matrix) -> Matrix {
+///
+/// Operands of any shape are zero-padded to the next power-of-two square,
+/// and the padding is trimmed from the product.
+fn multiply_strassen<T: Element>(a: &Matrix<T>, b: &Matrix<T>, threshold: usize) -> Matrix<T> {
     assert_eq!(a.cols, b.rows, "Matrix dimensions don't match for multiplication");
-    assert_eq!(a.rows, a.cols, "Strassen requires square matrices");
-    assert_eq!(b.rows, b.cols, "Strassen requires square matrices");
     
+    let n = a.rows.max(a.cols).max(b.cols).next_power_of_two();
+    if n == a.rows && n == a.cols && n == b.cols {
+        return strassen_square(a, b, threshold);
+    }
+    
+    strassen_square(&a.padded(n), &b.padded(n), threshold).trimmed(a.rows, b.cols)
+}
+
+/// Strassen recursion over power-of-two square matrices
+fn strassen_square<T: Element>(a: &Matrix<T>, b: &Matrix<T>, threshold: usize) -> Matrix<T> {
     let n = a.rows;
     
     // Base case - use naive multiplication for small matrices
-    if n <= 64 {
-        return multiply_naive(a, b);
-    }
-    
-    // Ensure matrix size is power of 2 (simplified implementation)
-    if !n.is_power_of_two() {
+    if n <= threshold.max(1) {
         return multiply_naive(a, b);
     }
     
@@ -262,42 +965,154 @@ fn multiply_strassen(a: &Matrix, b: &Matrix) -> Matrix {
     let (b11, b12, b21, b22) = split_matrix(b, half);
     
     // Compute the 7 products
-    let m1 = multiply_strassen(&matrix_add(&a11, &a22), &matrix_add(&b11, &b22));
-    let m2 = multiply_strassen(&matrix_add(&a21, &a22), &b11);
-    let m3 = multiply_strassen(&a11, &matrix_subtract(&b12, &b22));
-    let m4 = multiply_strassen(&a22, &matrix_subtract(&b21, &b11));
-    let m5 = multiply_strassen(&matrix_add(&a11, &a12), &b22);
-    let m6 = multiply_strassen(&matrix_subtract(&a21, &a11), &matrix_add(&b11, &b12));
-    let m7 = multiply_strassen(&matrix_subtract(&a12, &a22), &matrix_add(&b21, &b22));
-    
-    // Combine results
-    let c11 = matrix_add(&matrix_subtract(&matrix_add(&m1, &m4), &m5), &m7);
-    let c12 = matrix_add(&m3, &m5);
-    let c21 = matrix_add(&m2, &m4);
-    let c22 = matrix_add(&matrix_subtract(&matrix_add(&m1, &m3), &m2), &m6);
+    let m1 = strassen_square(&matrix_add(&a11, &a22), &matrix_add(&b11, &b22), threshold);
+    let m2 = strassen_square(&matrix_add(&a21, &a22), &b11, threshold);
+    let m3 = strassen_square(&a11, &matrix_subtract(&b12, &b22), threshold);
+    let m4 = strassen_square(&a22, &matrix_subtract(&b21, &b11), threshold);
+    let m5 = strassen_square(&matrix_add(&a11, &a12), &b22, threshold);
+    let m6 = strassen_square(&matrix_subtract(&a21, &a11), &matrix_add(&b11, &b12), threshold);
+    let m7 = strassen_square(&matrix_subtract(&a12, &a22), &matrix_add(&b21, &b22), threshold);
     
-    combine_matrices(&c11, &c12, &c21, &c22)
+    combine_strassen_products([m1, m2, m3, m4, m5, m6, m7])
 }
 
-fn split_matrix(m: &Matrix, half: usize) -> (Matrix, Matrix, Matrix, Matrix) {
-    let mut m11 = Matrix::new(half, half);
-    let mut m12 = Matrix::new(half, half);
-    let mut m21 = Matrix::new(half, half);
-    let mut m22 = Matrix::new(half, half);
+/// Winograd's form of Strassen: the same seven products, but intermediate
+/// sums are computed once and reused, cutting 18 additions per level to 15
+fn multiply_winograd<T: Element>(a: &Matrix<T>, b: &Matrix<T>, threshold: usize) -> Matrix<T> {
+    assert_eq!(a.cols, b.rows, "Matrix dimensions don't match for multiplication");
     
-    for i in 0..half {
-        for j in 0..half {
-            m11.set(i, j, m.get(i, j));
-            m12.set(i, j, m.get(i, j + half));
-            m21.set(i, j, m.get(i + half, j));
-            m22.set(i, j, m.get(i + half, j + half));
-        }
+    let n = a.rows.max(a.cols).max(b.cols).next_power_of_two();
+    if n == a.rows && n == a.cols && n == b.cols {
+        return winograd_square(a, b, threshold);
+    }
+    
+    winograd_square(&a.padded(n), &b.padded(n), threshold).trimmed(a.rows, b.cols)
+}
+
+fn winograd_square<T: Element>(a: &Matrix<T>, b: &Matrix<T>, threshold: usize) -> Matrix<T> {
+    let n = a.rows;
+    
+    // Base case - use naive multiplication for small matrices
+    if n <= threshold.max(1) {
+        return multiply_naive(a, b);
+    }
+    
+    let half = n / 2;
+    let (a11, a12, a21, a22) = split_matrix(a, half);
+    let (b11, b12, b21, b22) = split_matrix(b, half);
+    
+    // 8 additions on the operands
+    let s1 = matrix_add(&a21, &a22);
+    let s2 = matrix_subtract(&s1, &a11);
+    let s3 = matrix_subtract(&a11, &a21);
+    let s4 = matrix_subtract(&a12, &s2);
+    let t1 = matrix_subtract(&b12, &b11);
+    let t2 = matrix_subtract(&b22, &t1);
+    let t3 = matrix_subtract(&b22, &b12);
+    let t4 = matrix_subtract(&t2, &b21);
+    
+    let m1 = winograd_square(&a11, &b11, threshold);
+    let m2 = winograd_square(&a12, &b21, threshold);
+    let m3 = winograd_square(&s4, &b22, threshold);
+    let m4 = winograd_square(&a22, &t4, threshold);
+    let m5 = winograd_square(&s1, &t1, threshold);
+    let m6 = winograd_square(&s2, &t2, threshold);
+    let m7 = winograd_square(&s3, &t3, threshold);
+    
+    // 7 additions on the products, sharing u2 and u3
+    let u2 = matrix_add(&m1, &m6);
+    let u3 = matrix_add(&u2, &m7);
+    let u4 = matrix_add(&u2, &m5);
+    let c11 = matrix_add(&m1, &m2);
+    let c12 = matrix_add(&u4, &m3);
+    let c21 = matrix_subtract(&u3, &m4);
+    let c22 = matrix_add(&u3, &m5);
+    
+    combine_matrices(&c11, &c12, &c21, &c22)
+}
+
+/// Recursion levels that fan out through rayon; deeper levels run
+/// sequentially since 7³ = 343 tasks already saturate any desktop CPU
+const STRASSEN_PARALLEL_DEPTH: usize = 3;
+
+/// Strassen with the seven products of each level computed concurrently
+/// through nested `rayon::join`
+fn multiply_strassen_parallel<T: Element>(a: &Matrix<T>, b: &Matrix<T>, threshold: usize) -> Matrix<T> {
+    assert_eq!(a.cols, b.rows, "Matrix dimensions don't match for multiplication");
+    
+    let n = a.rows.max(a.cols).max(b.cols).next_power_of_two();
+    if n == a.rows && n == a.cols && n == b.cols {
+        return strassen_square_parallel(a, b, threshold, STRASSEN_PARALLEL_DEPTH);
+    }
+    
+    strassen_square_parallel(&a.padded(n), &b.padded(n), threshold, STRASSEN_PARALLEL_DEPTH)
+        .trimmed(a.rows, b.cols)
+}
+
+fn strassen_square_parallel<T: Element>(a: &Matrix<T>, b: &Matrix<T>, threshold: usize, depth: usize) -> Matrix<T> {
+    // Below the cutoff, or where the naive base case takes over, task
+    // overhead outweighs the work
+    if depth == 0 || a.rows <= threshold.max(1) {
+        return strassen_square(a, b, threshold);
+    }
+    
+    let half = a.rows / 2;
+    let (a11, a12, a21, a22) = split_matrix(a, half);
+    let (b11, b12, b21, b22) = split_matrix(b, half);
+    let recurse = |x: &Matrix<T>, y: &Matrix<T>| strassen_square_parallel(x, y, threshold, depth - 1);
+    
+    let ((m1, m2), ((m3, m4), (m5, (m6, m7)))) = rayon::join(
+        || rayon::join(
+            || recurse(&matrix_add(&a11, &a22), &matrix_add(&b11, &b22)),
+            || recurse(&matrix_add(&a21, &a22), &b11),
+        ),
+        || rayon::join(
+            || rayon::join(
+                || recurse(&a11, &matrix_subtract(&b12, &b22)),
+                || recurse(&a22, &matrix_subtract(&b21, &b11)),
+            ),
+            || rayon::join(
+                || recurse(&matrix_add(&a11, &a12), &b22),
+                || rayon::join(
+                    || recurse(&matrix_subtract(&a21, &a11), &matrix_add(&b11, &b12)),
+                    || recurse(&matrix_subtract(&a12, &a22), &matrix_add(&b21, &b22)),
+                ),
+            ),
+        ),
+    );
+    
+    combine_strassen_products([m1, m2, m3, m4, m5, m6, m7])
+}
+
+/// Assembles C from Strassen's seven products M1..M7
+fn combine_strassen_products<T: Element>([m1, m2, m3, m4, m5, m6, m7]: [Matrix<T>; 7]) -> Matrix<T> {
+    let c11 = matrix_add(&matrix_subtract(&matrix_add(&m1, &m4), &m5), &m7);
+    let c12 = matrix_add(&m3, &m5);
+    let c21 = matrix_add(&m2, &m4);
+    let c22 = matrix_add(&matrix_subtract(&matrix_add(&m1, &m3), &m2), &m6);
+    
+    combine_matrices(&c11, &c12, &c21, &c22)
+}
+
+fn split_matrix<T: Element>(m: &Matrix<T>, half: usize) -> (Matrix<T>, Matrix<T>, Matrix<T>, Matrix<T>) {
+    let mut m11 = Matrix::new(half, half);
+    let mut m12 = Matrix::new(half, half);
+    let mut m21 = Matrix::new(half, half);
+    let mut m22 = Matrix::new(half, half);
+    
+    for i in 0..half {
+        for j in 0..half {
+            m11.set(i, j, m.get(i, j));
+            m12.set(i, j, m.get(i, j + half));
+            m21.set(i, j, m.get(i + half, j));
+            m22.set(i, j, m.get(i + half, j + half));
+        }
     }
     
     (m11, m12, m21, m22)
 }
 
-fn matrix_add(a: &Matrix, b: &Matrix) -> Matrix {
+fn matrix_add<T: Element>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
     let mut result = Matrix::new(a.rows, a.cols);
     for i in 0..a.data.len() {
         result.data[i] = a.data[i] + b.data[i];
@@ -305,7 +1120,7 @@ fn matrix_add(a: &Matrix, b: &Matrix) -> Matrix {
     result
 }
 
-fn matrix_subtract(a: &Matrix, b: &Matrix) -> Matrix {
+fn matrix_subtract<T: Element>(a: &Matrix<T>, b: &Matrix<T>) -> Matrix<T> {
     let mut result = Matrix::new(a.rows, a.cols);
     for i in 0..a.data.len() {
         result.data[i] = a.data[i] - b.data[i];
@@ -313,7 +1128,7 @@ fn matrix_subtract(a: &Matrix, b: &Matrix) -> Matrix {
     result
 }
 
-fn combine_matrices(c11: &Matrix, c12: &Matrix, c21: &Matrix, c22: &Matrix) -> Matrix {
+fn combine_matrices<T: Element>(c11: &Matrix<T>, c12: &Matrix<T>, c21: &Matrix<T>, c22: &Matrix<T>) -> Matrix<T> {
     let half = c11.rows;
     let mut result = Matrix::new(2 * half, 2 * half);
     
@@ -329,127 +1144,660 @@ fn combine_matrices(c11: &Matrix, c12: &Matrix, c21: &Matrix, c22: &Matrix) -> M
     result
 }
 
-fn benchmark_algorithms(size: usize, iterations: usize) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🧮 Matrix Multiplication Benchmark");
-    println!("Matrix size: {}×{}", size, size);
-    println!("Iterations: {}", iterations);
-    println!("{}", "=".repeat(60));
+/// Every multiplication algorithm by its command-line name, with the label
+/// benchmark reports use; `multiply_by_name` dispatches on the first column
+const ALGORITHMS: [(&str, &str); 8] = [
+    ("naive", "Naive O(n³)"),
+    ("parallel", "Parallel Naive"),
+    ("transposed", "Transposed B"),
+    ("blocked", "Blocked"),
+    ("parallel-blocked", "Parallel Blocked"),
+    ("strassen", "Strassen O(n^2.8)"),
+    ("parallel-strassen", "Parallel Strassen"),
+    ("winograd", "Winograd (15 adds)"),
+];
+
+/// Multiplies with the algorithm registered under `name` in `ALGORITHMS`
+fn multiply_by_name<T: Element>(
+    name: &str,
+    a: &Matrix<T>,
+    b: &Matrix<T>,
+    block_size: usize,
+    strassen_threshold: usize,
+) -> Result<Matrix<T>, String> {
+    let product = match name {
+        "naive" => multiply_naive(a, b),
+        "parallel" => multiply_parallel_naive(a, b),
+        "transposed" => multiply_transposed(a, b),
+        "blocked" => multiply_blocked(a, b, block_size),
+        "parallel-blocked" => multiply_parallel_blocked(a, b, block_size),
+        "strassen" => multiply_strassen(a, b, strassen_threshold),
+        "parallel-strassen" => multiply_strassen_parallel(a, b, strassen_threshold),
+        "winograd" => multiply_winograd(a, b, strassen_threshold),
+        other => {
+            let known: Vec<&str> = ALGORITHMS.iter().map(|&(name, _)| name).collect();
+            return Err(format!("unknown algorithm '{}' (expected one of: {})", other, known.join(", ")));
+        }
+    };
+    Ok(product)
+}
+
+fn multiply_files(a_path: &str, b_path: &str, out: &str, algorithm: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let a = Matrix::<f64>::load(a_path)?;
+    let b = Matrix::<f64>::load(b_path)?;
+    check_dimensions(&a, &b)?;
     
-    // Generate test matrices
-    let a = Matrix::random(size, size, 42);
-    let b = Matrix::random(size, size, 84);
+    let start = Instant::now();
+    let product = multiply_by_name(algorithm, &a, &b, 64, STRASSEN_THRESHOLD)?;
+    let elapsed = start.elapsed().as_secs_f64();
     
-    // Benchmark algorithms
-    let algorithms: Vec<(&str, Box<dyn Fn(&Matrix, &Matrix) -> Matrix>)> = vec![
-        ("Naive O(n³)", Box::new(|a: &Matrix, b: &Matrix| multiply_naive(a, b))),
-        ("Parallel Naive", Box::new(|a: &Matrix, b: &Matrix| multiply_parallel_naive(a, b))),
-        ("Blocked (64)", Box::new(|a: &Matrix, b: &Matrix| multiply_blocked(a, b, 64))),
-        ("Parallel Blocked", Box::new(|a: &Matrix, b: &Matrix| multiply_parallel_blocked(a, b, 64))),
-    ];
+    product.save(out)?;
+    println!("✖️  {}×{} · {}×{} with {} in {:.3}s", a.rows, a.cols, b.rows, b.cols, algorithm, elapsed);
+    println!("📄 Product ({}×{}) saved to {}", product.rows, product.cols, out);
     
+    // Scalar summaries make products from different algorithms easy to compare
+    let trace = if product.rows == product.cols { format!("{:.6e}", product.trace()) } else { "n/a".to_string() };
+    println!("🔎 trace {}, Frobenius norm {:.6e}, max |c| {:.6e}", trace, product.frobenius_norm(), product.max_abs());
+    
+    Ok(())
+}
+
+type MultiplyFn<T> = Box<dyn Fn(&Matrix<T>, &Matrix<T>) -> Matrix<T>>;
+
+/// One algorithm's timing, emitted as a JSON object or CSV record by
+/// `benchmark --format json|csv`
+#[derive(Debug, Serialize, Deserialize)]
+struct BenchmarkResult {
+    /// Algorithm label, e.g. "Parallel Blocked"
+    name: String,
+    /// Matrix dimension n for the n×n operands
+    size: usize,
+    /// Mean wall-clock time per multiplication; the timing fields are null (empty in CSV)
+    /// when every measured run produced NaN or Inf
+    avg_seconds: Option<f64>,
+    /// Fastest measured run
+    min_seconds: Option<f64>,
+    /// Median of the measured runs
+    median_seconds: Option<f64>,
+    /// Sample standard deviation of the measured runs
+    stddev_seconds: Option<f64>,
+    /// 2n³ floating-point operations divided by avg_seconds, in units of 10⁹
+    gflops: Option<f64>,
+    /// Time of the first timed algorithm, normally naive, divided by this one's
+    speedup: Option<f64>,
+    /// Whether the product matched the naive result within --tolerance
+    verified: bool,
+    /// Frobenius norm of this product minus the naive one; null when it isn't finite
+    residual: Option<f64>,
+    /// Measured runs whose product held NaN or Inf, left out of the timings
+    non_finite_runs: usize,
+}
+
+/// Summary statistics over repeated timings, in seconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct TimingStats {
+    min: f64,
+    median: f64,
+    mean: f64,
+    stddev: f64,
+}
+
+impl TimingStats {
+    /// Summarizes `samples`; the standard deviation uses the n - 1 sample
+    /// form and is zero for a single run
+    fn from_samples(samples: &[f64]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let n = sorted.len();
+        
+        let median = match n {
+            0 => f64::NAN,
+            _ if n % 2 == 1 => sorted[n / 2],
+            _ => (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0,
+        };
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            sorted.iter().map(|t| (t - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        
+        TimingStats {
+            min: sorted.first().copied().unwrap_or(f64::NAN),
+            median,
+            mean,
+            stddev: variance.sqrt(),
+        }
+    }
+}
+
+/// Benchmark report format
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+/// Tile edge requested with --block-size
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BlockSize {
+    Auto,
+    Fixed(usize),
+}
+
+fn parse_block_size(value: &str) -> Result<BlockSize, String> {
+    if value.eq_ignore_ascii_case("auto") {
+        return Ok(BlockSize::Auto);
+    }
+    match value.parse() {
+        Ok(0) | Err(_) => Err(format!("expected \"auto\" or a positive tile size, got '{}'", value)),
+        Ok(block_size) => Ok(BlockSize::Fixed(block_size)),
+    }
+}
+
+/// L2 size assumed when the OS doesn't report one
+const DEFAULT_L2_BYTES: usize = 256 * 1024;
+
+/// Per-core L2 size from Linux sysfs, or `DEFAULT_L2_BYTES` elsewhere
+fn l2_cache_bytes() -> usize {
+    let Ok(entries) = fs::read_dir("/sys/devices/system/cpu/cpu0/cache") else {
+        return DEFAULT_L2_BYTES;
+    };
+    
+    entries
+        .flatten()
+        .find(|entry| {
+            fs::read_to_string(entry.path().join("level")).is_ok_and(|level| level.trim() == "2")
+        })
+        .and_then(|entry| fs::read_to_string(entry.path().join("size")).ok())
+        .and_then(|size| {
+            let size = size.trim();
+            match size.strip_suffix('K') {
+                Some(kib) => kib.parse::<usize>().ok().map(|kib| kib * 1024),
+                None => size.parse().ok(),
+            }
+        })
+        .unwrap_or(DEFAULT_L2_BYTES)
+}
+
+/// Largest power-of-two tile edge whose A, B and C tiles fit in the cache
+/// together, clamped to 16..=256
+fn optimal_block_size(cache_bytes: usize, element_bytes: usize) -> usize {
+    let max_elements = cache_bytes / (3 * element_bytes.max(1));
+    let mut block_size = 16;
+    while block_size < 256 && (2 * block_size) * (2 * block_size) <= max_elements {
+        block_size *= 2;
+    }
+    block_size
+}
+
+/// Explains block sizes that leave ragged edge tiles or exceed the matrix
+fn block_size_warning(size: usize, block_size: usize) -> Option<String> {
+    if block_size > size {
+        Some(format!("block size {} exceeds the {}×{} matrix, so blocking does nothing", block_size, size, size))
+    } else if !size.is_multiple_of(block_size) {
+        Some(format!("block size {} doesn't divide {}, leaving ragged edge tiles", block_size, size))
+    } else {
+        None
+    }
+}
+
+/// Knobs of the Benchmark subcommand
+struct BenchmarkOptions {
+    iterations: usize,
+    warmup: usize,
+    format: OutputFormat,
+    tolerance: Option<f64>,
+    strassen_threshold: usize,
+    block_size: BlockSize,
+    nan_equal: bool,
+}
+
+fn benchmark_algorithms<T: Element>(size: usize, input: &InputOptions, options: &BenchmarkOptions) -> Result<(), Box<dyn std::error::Error>> {
+    let BenchmarkOptions { iterations, warmup, format, tolerance, strassen_threshold, block_size, nan_equal } = *options;
+    let tolerance = tolerance.unwrap_or(T::TOLERANCE);
+    let block_size = match block_size {
+        BlockSize::Auto => optimal_block_size(l2_cache_bytes(), std::mem::size_of::<T>()),
+        BlockSize::Fixed(block_size) => block_size,
+    };
+    if let Some(warning) = block_size_warning(size, block_size) {
+        eprintln!("⚠️  {}", warning);
+    }
+    let algorithms = benchmark_suite::<T>(block_size, strassen_threshold);
+    let (a, b) = input.operands::<T>(size)?;
+    
+    let results = if format == OutputFormat::Text {
+        println!("🧮 Matrix Multiplication Benchmark");
+        println!("Matrix size: {}×{}", size, size);
+        println!("Iterations: {} (+{} warmup)", iterations, warmup);
+        println!("Element type: {}", T::NAME);
+        println!("Seed: {}, values in [{}, {})", input.seed, input.value_range.0, input.value_range.1);
+        match options.block_size {
+            BlockSize::Auto => println!("Block size: {} (auto, {} KiB L2)", block_size, l2_cache_bytes() / 1024),
+            BlockSize::Fixed(_) => println!("Block size: {}", block_size),
+        }
+        println!("{}", "=".repeat(60));
+        
+        run_benchmarks((&a, &b), iterations, warmup, algorithms, tolerance, nan_equal, |result| {
+            let check = if result.verified { "✓" } else { "✗" };
+            let residual = result.residual.map_or_else(|| "n/a".to_string(), |residual| format!("{:.2e}", residual));
+            match (result.avg_seconds, result.stddev_seconds, result.min_seconds, result.median_seconds, result.gflops, result.speedup) {
+                (Some(avg), Some(stddev), Some(min), Some(median), Some(gflops), Some(speedup)) => {
+                    println!("📊 {:<15} | {:.3}s ±{:.3} (min {:.3}, median {:.3}) | {:.2} GFLOPS | {:.2}x speedup | residual {} {}",
+                            result.name, avg, stddev, min, median, gflops, speedup, residual, check);
+                }
+                _ => println!("📊 {:<15} | no finite runs to time | residual {} {}", result.name, residual, check),
+            }
+        })
+    } else {
+        let results = run_benchmarks((&a, &b), iterations, warmup, algorithms, tolerance, nan_equal, |_| {});
+        write_benchmark_results(&results, format, io::stdout().lock())?;
+        results
+    };
+    
+    ensure_verified(&results, tolerance)
+}
+
+/// Fails when any algorithm's product diverged from the naive reference
+fn ensure_verified(results: &[BenchmarkResult], tolerance: f64) -> Result<(), Box<dyn std::error::Error>> {
+    let diverged: Vec<&str> = results
+        .iter()
+        .filter(|result| !result.verified)
+        .map(|result| result.name.as_str())
+        .collect();
+    
+    if diverged.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("{} diverged from the naive result beyond relative tolerance {:e}", diverged.join(", "), tolerance).into())
+    }
+}
+
+/// Writes machine-readable results: a JSON array, or CSV with a header row
+fn write_benchmark_results<W: Write>(results: &[BenchmarkResult], format: OutputFormat, mut out: W) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut out, results)?;
+            writeln!(out)?;
+        }
+        OutputFormat::Csv => {
+            let mut writer = csv::Writer::from_writer(out);
+            for result in results {
+                writer.serialize(result)?;
+            }
+            writer.flush()?;
+        }
+        OutputFormat::Text => unreachable!("text results are printed as they are measured"),
+    }
+    Ok(())
+}
+
+/// The algorithms `benchmark` times, naive first as the reference
+fn benchmark_suite<T: Element>(block_size: usize, strassen_threshold: usize) -> Vec<(String, MultiplyFn<T>)> {
+    ALGORITHMS
+        .iter()
+        .map(|&(name, label)| {
+            let label = match name {
+                "blocked" => format!("{} ({})", label, block_size),
+                _ => label.to_string(),
+            };
+            let algorithm: MultiplyFn<T> = Box::new(move |a: &Matrix<T>, b: &Matrix<T>| {
+                multiply_by_name(name, a, b, block_size, strassen_threshold).expect("name comes from ALGORITHMS")
+            });
+            (label, algorithm)
+        })
+        .collect()
+}
+
+/// Times every algorithm and checks its product against the first one's,
+/// calling `report` as each finishes
+fn run_benchmarks<T: Element>(
+    (a, b): (&Matrix<T>, &Matrix<T>),
+    iterations: usize,
+    warmup: usize,
+    algorithms: Vec<(String, MultiplyFn<T>)>,
+    tolerance: f64,
+    nan_equal: bool,
+    mut report: impl FnMut(&BenchmarkResult),
+) -> Vec<BenchmarkResult> {
+    let size = a.rows;
+    
+    let mut results = Vec::new();
     let mut baseline_time = None;
+    let mut reference: Option<Matrix<T>> = None;
     
     for (name, algorithm) in algorithms {
-        let mut total_time = 0.0;
+        let mut times = Vec::with_capacity(iterations);
+        let mut non_finite_runs = 0;
         let mut result = None;
         
-        for _ in 0..iterations {
+        // Warmup runs are timed like the rest but discarded, so the cold first
+        // run doesn't skew the statistics
+        for run in 0..warmup + iterations {
             let start = Instant::now();
-            let current_result = algorithm(&a, &b);
+            let current_result = algorithm(a, b);
             let elapsed = start.elapsed().as_secs_f64();
-            total_time += elapsed;
+            if run >= warmup {
+                // An overflowed product isn't a meaningful timing
+                if current_result.has_non_finite() {
+                    non_finite_runs += 1;
+                } else {
+                    times.push(elapsed);
+                }
+            }
             result = Some(current_result);
         }
         
-        let avg_time = total_time / iterations as f64;
-        let gflops = (2.0 * size.pow(3) as f64) / (avg_time * 1e9);
+        if non_finite_runs > 0 {
+            eprintln!("⚠️  {}: {} of {} runs produced NaN or Inf and were excluded from the timings",
+                    name, non_finite_runs, iterations);
+        }
+        
+        // With no finite run there is nothing to time; JSON can't hold the NaNs that would leave
+        let timing = (!times.is_empty()).then(|| TimingStats::from_samples(&times));
+        let avg_time = timing.map(|timing| timing.mean);
+        let gflops = avg_time.map(|avg_time| (2.0 * size.pow(3) as f64) / (avg_time * 1e9));
         
         // Calculate speedup relative to naive implementation
-        let speedup = if let Some(baseline) = baseline_time {
-            baseline / avg_time
+        let speedup = avg_time.map(|avg_time| *baseline_time.get_or_insert(avg_time) / avg_time);
+        
+        // Verify correctness (compare with naive result)
+        let (verified, residual) = match (result, &reference) {
+            (Some(current_result), Some(naive)) => (
+                current_result.verify_equal_with(naive, tolerance, nan_equal),
+                Some(matrix_subtract(&current_result, naive).frobenius_norm()).filter(|residual| residual.is_finite()),
+            ),
+            (Some(current_result), None) => {
+                reference = Some(current_result);
+                (true, Some(0.0))
+            }
+            (None, _) => (true, Some(0.0)),
+        };
+        
+        let measured = BenchmarkResult {
+            name,
+            size,
+            avg_seconds: avg_time,
+            min_seconds: timing.map(|timing| timing.min),
+            median_seconds: timing.map(|timing| timing.median),
+            stddev_seconds: timing.map(|timing| timing.stddev),
+            gflops,
+            speedup,
+            verified,
+            residual,
+            non_finite_runs,
+        };
+        report(&measured);
+        results.push(measured);
+    }
+    
+    results
+}
+
+/// One size step of `scaling`, also the row format of `--out`
+#[derive(Debug, Serialize, Deserialize)]
+struct ScalingRow {
+    size: usize,
+    naive_s: f64,
+    parallel_s: f64,
+    blocked_s: f64,
+    speedup: f64,
+}
+
+fn analyze_scaling<T: Element>(start_size: usize, end_size: usize, factor: usize, input: &InputOptions, out: Option<&str>) -> Result<(), Box<dyn std::error::Error>> {
+    println!("📈 Matrix Multiplication Scaling Analysis");
+    println!("Size range: {} to {}, factor: {}", start_size, end_size, factor);
+    println!("{}", "=".repeat(80));
+    println!("{:<8} {:<12} {:<12} {:<12} {:<12}", "Size", "Naive (s)", "Parallel (s)", "Blocked (s)", "Speedup");
+    println!("{}", "-".repeat(80));
+    
+    let mut writer = match out {
+        Some(path) => Some(csv::Writer::from_path(path)?),
+        None => None,
+    };
+    
+    // A factor below 2 would never reach end_size
+    let sizes = scaling_sizes(start_size, end_size, factor.max(2));
+    let progress = ScalingProgress::new(&sizes);
+    
+    for (step, &size) in sizes.iter().enumerate() {
+        let row = time_scaling_step::<T>(size, input, |algorithm| progress.timing(size, algorithm))?;
+        progress.finished(step, &row);
+        
+        progress.suspend(|| {
+            println!("{:<8} {:<12.3} {:<12.3} {:<12.3} {:<12.2}x", 
+                    row.size, row.naive_s, row.parallel_s, row.blocked_s, row.speedup);
+        });
+        if let Some(writer) = writer.as_mut() {
+            writer.serialize(&row)?;
+        }
+    }
+    progress.clear();
+    
+    if let (Some(mut writer), Some(path)) = (writer, out) {
+        writer.flush()?;
+        println!("📄 Scaling table saved to {}", path);
+    }
+    
+    Ok(())
+}
+
+fn scaling_sizes(start_size: usize, end_size: usize, factor: usize) -> Vec<usize> {
+    std::iter::successors(Some(start_size.max(1)), |&size| size.checked_mul(factor))
+        .take_while(|&size| size <= end_size)
+        .collect()
+}
+
+/// Progress bar over the size steps plus a spinner naming the algorithm
+/// being timed; both stay hidden unless stdout is a terminal
+struct ScalingProgress {
+    bar: ProgressBar,
+    spinner: ProgressBar,
+    /// Cubes of the sizes, for the O(n³) time-remaining model
+    work: Vec<f64>,
+    /// Seconds spent on each completed size
+    elapsed: RefCell<Vec<f64>>,
+}
+
+impl ScalingProgress {
+    fn new(sizes: &[usize]) -> Self {
+        let multi = if io::stdout().is_terminal() {
+            MultiProgress::new()
         } else {
-            baseline_time = Some(avg_time);
-            1.0
+            MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
         };
         
-        println!("📊 {:<15} | {:.3}s | {:.2} GFLOPS | {:.2}x speedup", 
-                name, avg_time, gflops, speedup);
+        let bar = multi.add(ProgressBar::new(sizes.len() as u64));
+        bar.set_style(
+            ProgressStyle::with_template("{bar:40.cyan/blue} {pos}/{len} sizes {msg}")
+                .expect("valid progress template"),
+        );
+        let spinner = multi.add(ProgressBar::new_spinner());
+        spinner.enable_steady_tick(Duration::from_millis(100));
         
-        // Verify correctness (compare with naive result)
-        if let Some(ref current_result) = result {
-            if name != "Naive O(n³)" {
-                // We'll implement verification later
-            }
+        ScalingProgress {
+            bar,
+            spinner,
+            work: sizes.iter().map(|&size| (size as f64).powi(3)).collect(),
+            elapsed: RefCell::new(Vec::new()),
         }
     }
     
-    // Add Strassen if size is appropriate
-    if size <= 512 && size.is_power_of_two() {
-        let mut total_time = 0.0;
+    fn timing(&self, size: usize, algorithm: &str) {
+        self.spinner.set_message(format!("timing {} at {}×{}", algorithm, size, size));
+    }
+    
+    fn finished(&self, step: usize, row: &ScalingRow) {
+        let mut elapsed = self.elapsed.borrow_mut();
+        elapsed.push(row.naive_s + row.parallel_s + row.blocked_s);
         
+        let remaining = estimate_remaining(&self.work[..=step], &elapsed, &self.work[step + 1..]);
+        self.bar.set_message(format!("~{:.0}s remaining (n³ model)", remaining));
+        self.bar.inc(1);
+    }
+    
+    fn suspend(&self, print: impl FnOnce()) {
+        self.bar.suspend(print);
+    }
+    
+    fn clear(&self) {
+        self.spinner.finish_and_clear();
+        self.bar.finish_and_clear();
+    }
+}
+
+/// Fits t = c·n³ to the completed steps and extrapolates over the rest
+fn estimate_remaining(done_work: &[f64], done_seconds: &[f64], remaining_work: &[f64]) -> f64 {
+    let done: f64 = done_work.iter().sum();
+    if done == 0.0 {
+        return 0.0;
+    }
+    let seconds_per_unit = done_seconds.iter().sum::<f64>() / done;
+    seconds_per_unit * remaining_work.iter().sum::<f64>()
+}
+
+fn time_scaling_step<T: Element>(size: usize, input: &InputOptions, mut on_algorithm: impl FnMut(&str)) -> Result<ScalingRow, MatrixError> {
+    let (a, b) = input.operands::<T>(size)?;
+    let mut time = |name: &str| {
+        on_algorithm(name);
+        let start = Instant::now();
+        let _result = multiply_by_name(name, &a, &b, 64, STRASSEN_THRESHOLD).expect("name comes from ALGORITHMS");
+        start.elapsed().as_secs_f64()
+    };
+    
+    let naive_time = time("naive");
+    let parallel_time = time("parallel");
+    let blocked_time = time("blocked");
+    
+    Ok(ScalingRow {
+        size,
+        naive_s: naive_time,
+        parallel_s: parallel_time,
+        blocked_s: blocked_time,
+        speedup: naive_time / parallel_time,
+    })
+}
+
+fn benchmark_matvec<T: Element>(size: usize, iterations: usize, input: &InputOptions) -> Result<(), Box<dyn std::error::Error>> {
+    println!("➡️  Matrix-Vector Multiplication Benchmark");
+    println!("Matrix size: {}×{}, iterations: {}", size, size, iterations);
+    println!("Element type: {}", T::NAME);
+    println!("{}", "=".repeat(60));
+    
+    let a = Matrix::<T>::random(size, size, input.seed, input.range()?);
+    let v = Matrix::<T>::random(1, size, input.seed.wrapping_add(42), input.range()?).data;
+    let kernels = [
+        ("Scalar", Matrix::multiply_vec as fn(&Matrix<T>, &[T]) -> Result<Vec<T>, MatrixError>),
+        ("Parallel", Matrix::multiply_vec_parallel),
+    ];
+    let mut baseline_time = None;
+    
+    for (name, kernel) in kernels {
+        let mut times = Vec::with_capacity(iterations);
         for _ in 0..iterations {
             let start = Instant::now();
-            let _result = multiply_strassen(&a, &b);
-            let elapsed = start.elapsed().as_secs_f64();
-            total_time += elapsed;
+            let _result = kernel(&a, &v)?;
+            times.push(start.elapsed().as_secs_f64());
         }
         
-        let avg_time = total_time / iterations as f64;
-        let gflops = (2.0 * size.pow(3) as f64) / (avg_time * 1e9);
-        let speedup = baseline_time.unwrap() / avg_time;
+        // A matvec is 2n² flops, one multiply and one add per element
+        let timing = TimingStats::from_samples(&times);
+        let gflops = (2.0 * size.pow(2) as f64) / (timing.mean * 1e9);
+        let speedup = *baseline_time.get_or_insert(timing.mean) / timing.mean;
         
-        println!("📊 {:<15} | {:.3}s | {:.2} GFLOPS | {:.2}x speedup", 
-                "Strassen O(n^2.8)", avg_time, gflops, speedup);
+        println!("📊 {:<9} | {:.6}s | {:.2} GFLOPS | {:.2}x speedup", name, timing.mean, gflops, speedup);
     }
     
     Ok(())
 }
 
-fn analyze_scaling(start_size: usize, end_size: usize, factor: usize) -> Result<(), Box<dyn std::error::Error>> {
-    println!("📈 Matrix Multiplication Scaling Analysis");
-    println!("Size range: {} to {}, factor: {}", start_size, end_size, factor);
+/// Solves a random system with a known solution, then multiplies back to check it
+fn solve_system<T: Element>(size: usize, input: &InputOptions) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🧮 Linear System Solver (LU with partial pivoting)");
+    println!("System size: {}×{}", size, size);
+    println!("Element type: {}", T::NAME);
+    println!("{}", "=".repeat(60));
+    
+    let a = Matrix::<T>::random(size, size, input.seed, input.range()?);
+    let expected = Matrix::<T>::random(1, size, input.seed.wrapping_add(42), input.range()?).data;
+    let b = a.multiply_vec(&expected)?;
+    
+    let start = Instant::now();
+    let x = a.solve(&b)?;
+    let elapsed = start.elapsed().as_secs_f64();
+    
+    // Factoring dominates at 2n³/3 flops; the two substitutions add only 2n²
+    let gflops = (2.0 * size.pow(3) as f64 / 3.0) / (elapsed * 1e9);
+    println!("📊 Solved in {:.6}s | {:.2} GFLOPS", elapsed, gflops);
+    
+    let ax = a.multiply_vec(&x)?;
+    let residual = ax.iter().zip(&b).map(|(&p, &q)| (p - q).to_f64().powi(2)).sum::<f64>().sqrt();
+    let b_norm = b.iter().map(|v| v.to_f64().powi(2)).sum::<f64>().sqrt();
+    let relative = residual / b_norm.max(f64::MIN_POSITIVE);
+    let max_error = x.iter().zip(&expected).map(|(&p, &q)| (p - q).to_f64().abs()).fold(0.0, f64::max);
+    
+    let verified = relative <= T::TOLERANCE;
+    println!("{} ‖Ax − b‖ = {:.3e} (relative {:.3e})", if verified { "✓" } else { "✗" }, residual, relative);
+    println!("Largest error against the known solution: {:.3e}", max_error);
+    
+    if !verified {
+        return Err(format!("relative residual {:.3e} exceeds tolerance {:e}", relative, T::TOLERANCE).into());
+    }
+    
+    Ok(())
+}
+
+/// Powers of two up to `max_threads`, plus `max_threads` itself
+fn thread_counts(max_threads: usize) -> Vec<usize> {
+    let mut counts: Vec<usize> = (0..)
+        .map(|shift| 1 << shift)
+        .take_while(|&count| count < max_threads)
+        .collect();
+    counts.push(max_threads.max(1));
+    counts
+}
+
+fn analyze_thread_scaling<T: Element>(size: usize, max_threads: usize, input: &InputOptions) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🧵 Strong Scaling Analysis");
+    println!("Matrix size: {}×{}, threads: 1 to {}", size, size, max_threads);
     println!("{}", "=".repeat(80));
-    println!("{:<8} {:<12} {:<12} {:<12} {:<12}", "Size", "Naive (s)", "Parallel (s)", "Blocked (s)", "Speedup");
+    println!("{:<8} {:<16} {:<16} {:<12} {:<12}", "Threads", "Par Naive (s)", "Par Blocked (s)", "Speedup", "Efficiency");
     println!("{}", "-".repeat(80));
     
-    let mut size = start_size;
-    while size <= end_size {
-        let a = Matrix::random(size, size, 42);
-        let b = Matrix::random(size, size, 84);
-        
-        // Time naive
-        let start = Instant::now();
-        let _naive_result = multiply_naive(&a, &b);
-        let naive_time = start.elapsed().as_secs_f64();
-        
-        // Time parallel
-        let start = Instant::now();
-        let _parallel_result = multiply_parallel_naive(&a, &b);
-        let parallel_time = start.elapsed().as_secs_f64();
-        
-        // Time blocked
-        let start = Instant::now();
-        let _blocked_result = multiply_blocked(&a, &b, 64);
-        let blocked_time = start.elapsed().as_secs_f64();
-        
-        let speedup = naive_time / parallel_time;
+    let (a, b) = input.operands::<T>(size)?;
+    let mut single_thread_time = None;
+    
+    for threads in thread_counts(max_threads) {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build()?;
+        let (naive_time, blocked_time) = pool.install(|| {
+            let start = Instant::now();
+            let _naive_result = multiply_parallel_naive(&a, &b);
+            let naive_time = start.elapsed().as_secs_f64();
+            
+            let start = Instant::now();
+            let _blocked_result = multiply_parallel_blocked(&a, &b, 64);
+            (naive_time, start.elapsed().as_secs_f64())
+        });
         
-        println!("{:<8} {:<12.3} {:<12.3} {:<12.3} {:<12.2}x", 
-                size, naive_time, parallel_time, blocked_time, speedup);
+        // Speedup and efficiency follow the blocked kernel against its own 1-thread run
+        let baseline = *single_thread_time.get_or_insert(blocked_time);
+        let speedup = baseline / blocked_time;
         
-        size *= factor;
+        let efficiency = format!("{:.0}%", 100.0 * speedup / threads as f64);
+        println!("{:<8} {:<16.3} {:<16.3} {:<12} {:<12}", 
+                threads, naive_time, blocked_time, format!("{:.2}x", speedup), efficiency);
     }
     
     Ok(())
 }
 
-fn demonstrate_techniques(size: usize) -> Result<(), Box<dyn std::error::Error>> {
+fn demonstrate_techniques<T: Element>(size: usize, input: &InputOptions) -> Result<(), Box<dyn std::error::Error>> {
     println!("🔧 Matrix Multiplication Optimization Techniques");
     println!("Matrix size: {}×{}", size, size);
     println!("{}", "=".repeat(60));
     
-    let a = Matrix::random(size, size, 42);
-    let b = Matrix::random(size, size, 84);
+    let (a, b) = input.operands::<T>(size)?;
     
     // Test different block sizes
     println!("🧱 Block Size Analysis:");
@@ -464,6 +1812,13 @@ fn demonstrate_techniques(size: usize) -> Result<(), Box<dyn std::error::Error>>
         }
     }
     
+    println!("  Heuristic pick (--block-size auto): {} for a {} KiB L2",
+            optimal_block_size(l2_cache_bytes(), std::mem::size_of::<T>()), l2_cache_bytes() / 1024);
+    
+    // Blocking only reorders the arithmetic, so A·I must reproduce A exactly
+    let identity_holds = multiply_blocked(&a, &Matrix::identity(size), 64).verify_equal(&a, 0.0);
+    println!("  Identity check (A·I = A): {}", if identity_holds { "✓" } else { "✗" });
+    
     // Memory access patterns
     println!("\n🧠 Memory Access Patterns:");
     println!("  Row-major access: Optimized for CPU cache lines");
@@ -473,13 +1828,12 @@ fn demonstrate_techniques(size: usize) -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
-fn analyze_memory_patterns(size: usize) -> Result<(), Box<dyn std::error::Error>> {
+fn analyze_memory_patterns<T: Element>(size: usize, input: &InputOptions) -> Result<(), Box<dyn std::error::Error>> {
     println!("💾 Memory Access Pattern Analysis");
     println!("Matrix size: {}×{}", size, size);
     println!("{}", "=".repeat(60));
     
-    let a = Matrix::random(size, size, 42);
-    let b = Matrix::random(size, size, 84);
+    let (a, b) = input.operands::<T>(size)?;
     
     // Analyze cache effects with different access patterns
     println!("🔄 Cache Performance Analysis:");
@@ -489,9 +1843,21 @@ fn analyze_memory_patterns(size: usize) -> Result<(), Box<dyn std::error::Error>
     let _result1 = multiply_naive(&a, &b);  // ijk order
     let ijk_time = start.elapsed().as_secs_f64();
     
-    // We would implement ikj, jik, etc. orders here for comparison
     println!("  IJK order: {:.3}s (standard row-major)", ijk_time);
     
+    let orders = [
+        ("IKJ order", multiply_ikj as fn(&Matrix<T>, &Matrix<T>) -> Matrix<T>, "streams rows of B"),
+        ("JIK order", multiply_jik, "column-wise output"),
+        ("Recursive", multiply_recursive, "cache-oblivious halving"),
+    ];
+    for (label, multiply, note) in orders {
+        let start = Instant::now();
+        let _result = multiply(&a, &b);
+        let time = start.elapsed().as_secs_f64();
+        
+        println!("  {}: {:.3}s ({}, {:.2}x efficiency)", label, time, note, ijk_time / time);
+    }
+    
     // Block analysis
     println!("\n🧱 Block Size vs Performance:");
     let block_sizes = [16, 32, 64, 128];
@@ -513,3 +1879,389 @@ fn analyze_memory_patterns(size: usize) -> Result<(), Box<dyn std::error::Error>
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn temp_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("matrix-multiplier-{}-{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+    
+    #[test]
+    fn test_parallel_blocked_matches_naive() {
+        let a = Matrix::random(256, 256, 42, -1.0..1.0);
+        let b = Matrix::random(256, 256, 84, -1.0..1.0);
+        let expected = multiply_naive(&a, &b);
+        
+        assert!(multiply_parallel_blocked(&a, &b, 64).verify_equal(&expected, 1e-9));
+        // A block size that doesn't divide the matrix leaves ragged edge blocks
+        assert!(multiply_parallel_blocked(&a, &b, 48).verify_equal(&expected, 1e-9));
+    }
+    
+    #[test]
+    fn test_f32_matrices_match_naive() {
+        let a = Matrix::<f32>::random(64, 64, 42, -1.0..1.0);
+        let b = Matrix::<f32>::random(64, 64, 84, -1.0..1.0);
+        
+        assert!(multiply_blocked(&a, &b, 16).verify_equal(&multiply_naive(&a, &b), f32::TOLERANCE));
+    }
+    
+    #[test]
+    fn test_transpose_handles_non_square() {
+        let m = Matrix::from_fn(2, 3, |i, j| (i * 3 + j) as f64);
+        let t = m.transpose();
+        
+        assert_eq!((t.rows, t.cols), (3, 2));
+        assert_eq!(t.data, vec![0.0, 3.0, 1.0, 4.0, 2.0, 5.0]);
+        assert!(t.transpose().verify_equal(&m, 0.0));
+    }
+    
+    #[test]
+    fn test_transposed_matches_naive() {
+        let a = Matrix::random(37, 53, 1, -1.0..1.0);
+        let b = Matrix::random(53, 29, 2, -1.0..1.0);
+        
+        assert!(multiply_transposed(&a, &b).verify_equal(&multiply_naive(&a, &b), 1e-9));
+    }
+    
+    #[test]
+    fn test_csv_and_json_round_trip() {
+        let m = Matrix::random(3, 4, 7, -10.0..10.0);
+        
+        for name in ["round-trip.csv", "round-trip.json"] {
+            let path = temp_path(name);
+            m.save(&path).unwrap();
+            let loaded = Matrix::<f64>::load(&path).unwrap();
+            fs::remove_file(&path).unwrap();
+            
+            assert_eq!((loaded.rows, loaded.cols), (3, 4));
+            assert_eq!(loaded.data, m.data, "{}", name);
+        }
+    }
+    
+    #[test]
+    fn test_ragged_rows_and_mismatched_dimensions_are_errors() {
+        let path = temp_path("ragged.json");
+        fs::write(&path, "[[1, 2], [3]]").unwrap();
+        let ragged = Matrix::<f64>::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(ragged, Err(MatrixError::InvalidData(_))));
+        
+        let a = Matrix::<f64>::new(2, 3);
+        let b = Matrix::<f64>::new(2, 3);
+        assert!(matches!(check_dimensions(&a, &b), Err(MatrixError::DimensionMismatch(_))));
+    }
+    
+    #[test]
+    fn test_json_benchmark_results_parse_back() {
+        let (a, b) = (Matrix::random(16, 16, 1, -1.0..1.0), Matrix::random(16, 16, 2, -1.0..1.0));
+        let results = run_benchmarks::<f64>((&a, &b), 1, 0, benchmark_suite(8, STRASSEN_THRESHOLD), 1e-6, false, |_| {});
+        let mut out = Vec::new();
+        write_benchmark_results(&results, OutputFormat::Json, &mut out).unwrap();
+        
+        let parsed: Vec<BenchmarkResult> = serde_json::from_slice(&out).unwrap();
+        assert_eq!(parsed.len(), results.len());
+        assert_eq!(parsed[0].name, "Naive O(n³)");
+        assert_eq!(parsed[0].speedup, Some(1.0));
+        assert!(parsed.iter().all(|result| result.size == 16 && result.avg_seconds.is_some_and(|avg| avg > 0.0)));
+    }
+    
+    #[test]
+    fn test_loop_orders_match_naive() {
+        let a = Matrix::random(45, 31, 3, -1.0..1.0);
+        let b = Matrix::random(31, 27, 4, -1.0..1.0);
+        let expected = multiply_naive(&a, &b);
+        
+        assert!(multiply_ikj(&a, &b).verify_equal(&expected, 1e-9));
+        assert!(multiply_jik(&a, &b).verify_equal(&expected, 1e-9));
+    }
+    
+    #[test]
+    fn test_broken_algorithm_fails_verification() {
+        let algorithms: Vec<(String, MultiplyFn<f64>)> = vec![
+            ("Naive O(n³)".to_string(), Box::new(|a: &Matrix<f64>, b: &Matrix<f64>| multiply_naive(a, b))),
+            ("Off By One".to_string(), Box::new(|a: &Matrix<f64>, b: &Matrix<f64>| {
+                let mut result = multiply_naive(a, b);
+                result.data[0] += 1.0;
+                result
+            })),
+        ];
+        let (a, b) = (Matrix::random(8, 8, 1, -1.0..1.0), Matrix::random(8, 8, 2, -1.0..1.0));
+        let results = run_benchmarks((&a, &b), 1, 0, algorithms, 1e-6, false, |_| {});
+        
+        assert!(results[0].verified);
+        assert!(!results[1].verified);
+        assert!(ensure_verified(&results, 1e-6).unwrap_err().to_string().contains("Off By One"));
+    }
+    
+    #[test]
+    fn test_timing_stats_over_known_sample() {
+        let stats = TimingStats::from_samples(&[4.0, 1.0, 10.0, 3.0, 2.0]);
+        
+        assert_eq!((stats.min, stats.median, stats.mean), (1.0, 3.0, 4.0));
+        // Squared deviations sum to 50, over n - 1 = 4
+        assert!((stats.stddev - 12.5f64.sqrt()).abs() < 1e-12);
+        
+        assert_eq!(TimingStats::from_samples(&[1.0, 2.0, 3.0, 4.0]).median, 2.5);
+        assert_eq!(TimingStats::from_samples(&[0.5]).stddev, 0.0);
+    }
+    
+    #[test]
+    fn test_padded_strassen_matches_naive() {
+        let a = Matrix::random(96, 96, 5, -1.0..1.0);
+        let b = Matrix::random(96, 96, 6, -1.0..1.0);
+        let product = multiply_strassen(&a, &b, 16);
+        
+        assert_eq!((product.rows, product.cols), (96, 96));
+        assert!(product.verify_equal(&multiply_naive(&a, &b), 1e-9));
+        
+        // Rectangular operands pad to a square too
+        let c = Matrix::random(20, 9, 7, -1.0..1.0);
+        let d = Matrix::random(9, 13, 8, -1.0..1.0);
+        assert!(multiply_strassen(&c, &d, 4).verify_equal(&multiply_naive(&c, &d), 1e-9));
+    }
+    
+    #[test]
+    fn test_parallel_strassen_matches_sequential() {
+        let a = Matrix::random(100, 100, 9, -1.0..1.0);
+        let b = Matrix::random(100, 100, 10, -1.0..1.0);
+        let parallel = multiply_strassen_parallel(&a, &b, 8);
+        
+        assert!(parallel.verify_equal(&multiply_naive(&a, &b), 1e-9));
+        // Same recursion, same arithmetic: only the scheduling differs
+        assert_eq!(parallel.data, multiply_strassen(&a, &b, 8).data);
+    }
+    
+    #[test]
+    fn test_recursive_matches_naive_for_odd_shapes() {
+        let a = Matrix::random(101, 67, 11, -1.0..1.0);
+        let b = Matrix::random(67, 83, 12, -1.0..1.0);
+        
+        assert!(multiply_recursive(&a, &b).verify_equal(&multiply_naive(&a, &b), 1e-9));
+    }
+    
+    #[test]
+    fn test_thread_counts_double_up_to_the_maximum() {
+        assert_eq!(thread_counts(1), vec![1]);
+        assert_eq!(thread_counts(6), vec![1, 2, 4, 6]);
+        assert_eq!(thread_counts(8), vec![1, 2, 4, 8]);
+    }
+    
+    #[test]
+    fn test_matvec_on_known_values() {
+        let a = Matrix::from_vec(2, 3, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        let v = [1.0, 0.0, -1.0];
+        
+        assert_eq!(a.multiply_vec(&v).unwrap(), vec![-2.0, -2.0]);
+        assert_eq!(a.multiply_vec_parallel(&v).unwrap(), vec![-2.0, -2.0]);
+        assert!(matches!(a.multiply_vec(&[1.0, 2.0]), Err(MatrixError::DimensionMismatch(_))));
+    }
+    
+    #[test]
+    fn test_identity_is_a_multiplicative_identity() {
+        let a = Matrix::random(5, 7, 13, -1.0..1.0);
+        
+        assert_eq!(multiply_naive(&Matrix::identity(5), &a).data, a.data);
+        assert_eq!(multiply_naive(&a, &Matrix::identity(7)).data, a.data);
+    }
+    
+    #[test]
+    fn test_from_vec_validates_length() {
+        assert!(Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0, 4.0]).is_ok());
+        assert!(matches!(Matrix::from_vec(2, 2, vec![1.0, 2.0, 3.0]), Err(MatrixError::DimensionMismatch(_))));
+    }
+    
+    #[test]
+    fn test_optimal_block_size_fits_typical_caches() {
+        for cache_kib in [256, 512, 1024, 2048] {
+            for element_bytes in [4, 8] {
+                let block_size = optimal_block_size(cache_kib * 1024, element_bytes);
+                assert!(block_size.is_power_of_two(), "{}", block_size);
+                assert!((16..=256).contains(&block_size), "{}", block_size);
+                assert!(3 * block_size * block_size * element_bytes <= cache_kib * 1024);
+            }
+        }
+        
+        // Halving the element size can only grow the tile
+        assert_eq!(optimal_block_size(256 * 1024, 8), 64);
+        assert_eq!(optimal_block_size(256 * 1024, 4), 128);
+        assert_eq!(parse_block_size("auto"), Ok(BlockSize::Auto));
+        assert_eq!(parse_block_size("48"), Ok(BlockSize::Fixed(48)));
+        assert!(parse_block_size("0").is_err());
+    }
+    
+    #[test]
+    fn test_non_finite_results_are_detected_and_excluded() {
+        let mut a = Matrix::<f64>::identity(3);
+        a.set(1, 1, f64::INFINITY);
+        let product = multiply_naive(&a, &Matrix::from_fn(3, 3, |i, j| (i * j) as f64));
+        
+        // Inf · 0 is NaN, so row 1 holds both NaN and Inf
+        assert!(product.has_non_finite());
+        assert!(product.data[4].is_infinite() && product.data[3].is_nan());
+        assert!(!product.verify_equal(&product, 1e-6));
+        assert!(product.verify_equal_with(&product, 1e-6, true));
+        
+        let algorithms: Vec<(String, MultiplyFn<f64>)> = vec![
+            ("Naive O(n³)".to_string(), Box::new(|a: &Matrix<f64>, b: &Matrix<f64>| multiply_naive(a, b))),
+            ("Overflowing".to_string(), Box::new(|a: &Matrix<f64>, _: &Matrix<f64>| {
+                Matrix::from_fn(a.rows, a.cols, |_, _| f64::INFINITY)
+            })),
+        ];
+        let (a, b) = (Matrix::random(8, 8, 1, -1.0..1.0), Matrix::random(8, 8, 2, -1.0..1.0));
+        let results = run_benchmarks((&a, &b), 2, 0, algorithms, 1e-6, false, |_| {});
+        
+        assert_eq!(results[0].non_finite_runs, 0);
+        assert_eq!(results[1].non_finite_runs, 2);
+        assert!(results[1].gflops.is_none() && results[1].residual.is_none() && !results[1].verified);
+        
+        // All-null timings still make a record that parses back
+        let mut out = Vec::new();
+        write_benchmark_results(&results, OutputFormat::Json, &mut out).unwrap();
+        let parsed: Vec<BenchmarkResult> = serde_json::from_slice(&out).unwrap();
+        assert_eq!(parsed[1].avg_seconds, None);
+        
+        assert!(Cli::try_parse_from(["matrix-multiplier", "benchmark", "8", "--iterations", "0"]).is_err());
+    }
+    
+    #[test]
+    fn test_scaling_csv_parses_back() {
+        let path = temp_path("scaling.csv");
+        let input = InputOptions { seed: 42, value_range: (-1.0, 1.0) };
+        analyze_scaling::<f64>(4, 8, 2, &input, Some(&path)).unwrap();
+        
+        let rows: Vec<ScalingRow> = csv::Reader::from_path(&path)
+            .unwrap()
+            .deserialize()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let header = fs::read_to_string(&path).unwrap().lines().next().unwrap().to_string();
+        fs::remove_file(&path).unwrap();
+        
+        assert_eq!(header, "size,naive_s,parallel_s,blocked_s,speedup");
+        assert_eq!(rows.iter().map(|row| row.size).collect::<Vec<_>>(), vec![4, 8]);
+        assert!(rows.iter().all(|row| row.naive_s > 0.0 && row.speedup > 0.0));
+    }
+    
+    #[test]
+    fn test_winograd_matches_naive() {
+        let a = Matrix::random(128, 128, 14, -1.0..1.0);
+        let b = Matrix::random(128, 128, 15, -1.0..1.0);
+        assert!(multiply_winograd(&a, &b, 16).verify_equal(&multiply_naive(&a, &b), 1e-9));
+        
+        let c = Matrix::random(50, 70, 16, -1.0..1.0);
+        let d = Matrix::random(70, 30, 17, -1.0..1.0);
+        assert!(multiply_winograd(&c, &d, 8).verify_equal(&multiply_naive(&c, &d), 1e-9));
+    }
+    
+    #[test]
+    fn test_remaining_time_follows_the_cubic_model() {
+        assert_eq!(scaling_sizes(64, 1024, 2), vec![64, 128, 256, 512, 1024]);
+        assert_eq!(scaling_sizes(100, 99, 2), Vec::<usize>::new());
+        
+        // 1 s for 8 units of work predicts 8 s for the next 64
+        assert_eq!(estimate_remaining(&[8.0], &[1.0], &[64.0]), 8.0);
+        assert_eq!(estimate_remaining(&[8.0, 64.0], &[1.0, 8.0], &[]), 0.0);
+    }
+    
+    #[test]
+    fn test_same_seed_and_range_give_identical_matrices() {
+        let input = InputOptions { seed: 7, value_range: parse_value_range("-100:250").unwrap() };
+        let (a1, b1) = input.operands::<f64>(12).unwrap();
+        let (a2, b2) = input.operands::<f64>(12).unwrap();
+        
+        assert_eq!(a1.data.iter().map(|x| x.to_bits()).collect::<Vec<_>>(),
+                   a2.data.iter().map(|x| x.to_bits()).collect::<Vec<_>>());
+        assert_eq!(b1.data, b2.data);
+        assert_ne!(a1.data, b1.data);
+        assert!(a1.data.iter().all(|x| (-100.0..250.0).contains(x)));
+        
+        assert!(parse_value_range("5:1").is_err());
+        assert!(parse_value_range("1").is_err());
+    }
+    
+    #[test]
+    fn test_trace_norm_and_max_abs() {
+        let m = Matrix::from_vec(2, 2, vec![1.0, -2.0, 3.0, 4.0]).unwrap();
+        
+        assert_eq!(m.trace(), 5.0);
+        assert_eq!(m.frobenius_norm(), 30.0f64.sqrt());
+        assert_eq!(m.max_abs(), 4.0);
+        assert_eq!(Matrix::<f64>::from_vec(1, 3, vec![3.0, 0.0, -4.0]).unwrap().frobenius_norm(), 5.0);
+        assert_eq!(Matrix::<f64>::identity(6).trace(), 6.0);
+    }
+    
+    #[test]
+    fn test_lu_solves_small_system() {
+        // Column 1's largest entry is in row 2, so the first step must pivot
+        let a = Matrix::<f64>::from_vec(3, 3, vec![2.0, 1.0, 1.0, 4.0, -6.0, 0.0, -2.0, 7.0, 2.0]).unwrap();
+        let (lower, upper, perm) = a.lu_decompose().unwrap();
+        
+        assert_eq!(perm[0], 1);
+        let permuted = Matrix::from_fn(3, 3, |i, j| a.get(perm[i], j));
+        assert!(multiply_naive(&lower, &upper).verify_equal(&permuted, 1e-12));
+        assert!((0..3).all(|i| lower.get(i, i) == 1.0 && (i + 1..3).all(|j| lower.get(i, j) == 0.0)));
+        assert!((0..3).all(|i| (0..i).all(|j| upper.get(i, j) == 0.0)));
+        
+        let x = a.solve(&[7.0, -8.0, 18.0]).unwrap();
+        for (got, want) in x.iter().zip([1.0, 2.0, 3.0]) {
+            assert!((got - want).abs() < 1e-12, "{:?}", x);
+        }
+        
+        let singular = Matrix::from_vec(2, 2, vec![1.0, 2.0, 2.0, 4.0]).unwrap();
+        assert!(matches!(singular.lu_decompose(), Err(MatrixError::ComputationError(_))));
+        assert!(matches!(a.solve(&[1.0, 2.0]), Err(MatrixError::DimensionMismatch(_))));
+    }
+    
+    #[test]
+    fn test_every_registered_name_multiplies_correctly() {
+        let a = Matrix::<f64>::random(10, 10, 3, -1.0..1.0);
+        let b = Matrix::<f64>::random(10, 10, 4, -1.0..1.0);
+        let expected = multiply_naive(&a, &b);
+        
+        for (name, _) in ALGORITHMS {
+            let product = multiply_by_name(name, &a, &b, 4, 4).unwrap();
+            assert!(product.verify_equal(&expected, 1e-9), "{} disagrees with naive", name);
+        }
+        
+        let error = multiply_by_name("bogus", &a, &b, 4, 4).unwrap_err();
+        assert!(error.contains("bogus") && error.contains("winograd"), "{}", error);
+    }
+    
+    #[test]
+    fn test_value_ranges_the_sampler_cannot_use_are_rejected() {
+        // Both bounds are finite f64s, but the width overflows
+        assert!(parse_value_range("-1e308:1e308").is_err());
+        
+        let input = |range: &str| InputOptions { seed: 1, value_range: parse_value_range(range).unwrap() };
+        // Fine as f64, but the f32 bounds collapse to 0:0 or overflow to infinity
+        assert!(input("0:1e-50").range::<f64>().is_ok());
+        assert!(matches!(input("0:1e-50").range::<f32>(), Err(MatrixError::InvalidData(_))));
+        assert!(input("1e39:1e40").range::<f64>().is_ok());
+        assert!(input("1e39:1e40").operands::<f32>(2).is_err());
+        assert!(input("-3e38:3e38").range::<f32>().is_err());
+        assert_eq!(input("-2:3").range::<f32>().unwrap(), -2.0f32..3.0);
+    }
+    
+    #[test]
+    fn test_tolerance_scales_with_the_product() {
+        let range = -1e6..1e6;
+        let (a, b) = (Matrix::<f64>::random(64, 64, 1, range.clone()), Matrix::<f64>::random(64, 64, 2, range));
+        let expected = multiply_naive(&a, &b);
+        let strassen = multiply_strassen(&a, &b, 8);
+        
+        // Elements near 1e13 round differently by far more than 1e-6 in absolute terms
+        assert!(matrix_subtract(&strassen, &expected).max_abs() > 1e-6);
+        assert!(strassen.verify_equal(&expected, 1e-6));
+        
+        let mut off = Matrix::from_vec(1, 2, vec![1e6, 0.5]).unwrap();
+        let reference = Matrix::from_vec(1, 2, vec![1e6, 0.0]).unwrap();
+        assert!(!off.verify_equal(&reference, 1e-7));
+        off.set(0, 1, 0.05);
+        assert!(off.verify_equal(&reference, 1e-7));
+    }
+}
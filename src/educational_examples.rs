@@ -1,11 +1,28 @@
 // Educational Examples: From Hardware to Software Complexity
 // This file demonstrates the conceptual bridge from assembly to high-level thinking
 
-use std::sync::{Arc, Mutex};
+use std::fmt;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, RwLock, TryLockError};
 use std::thread;
+use std::time::Duration;
 
 /// LEVEL 1: Assembly-like thinking (explicit, manual)
 mod assembly_style {
+    use std::ptr::{self, addr_of_mut};
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Mutex, MutexGuard};
+    use std::thread;
+    
+    // Only one caller may touch this module's statics at a time, so runs don't corrupt each other
+    static STATIC_MUT_LOCK: Mutex<()> = Mutex::new(());
+    
+    /// Hold this while calling unsafe_counter or the racy demos to keep other callers out
+    pub fn serial_guard() -> MutexGuard<'static, ()> {
+        STATIC_MUT_LOCK.lock().unwrap_or_else(|e| e.into_inner())
+    }
+    
     pub fn unsafe_counter() -> u64 {
         // This is what assembly programmers deal with
         static mut COUNTER: u64 = 0;
@@ -15,10 +32,61 @@ mod assembly_style {
             COUNTER
         }
     }
+    
+    static mut RACY_TOTAL: u64 = 0;
+    
+    #[derive(Debug)]
+    pub struct RaceReport {
+        pub expected: u64,
+        pub racy_total: u64,
+        pub atomic_total: u64,
+    }
+    
+    impl RaceReport {
+        /// Increments that vanished because two threads overwrote each other
+        pub fn lost_updates(&self) -> u64 {
+            self.expected - self.racy_total
+        }
+    }
+    
+    /// Hammers a plain `static mut` and an AtomicU64 with the same workload
+    pub fn race_comparison(threads: usize, increments: u64) -> RaceReport {
+        let _serial = serial_guard();
+        let atomic = AtomicU64::new(0);
+        
+        // SAFETY: none, on purpose - this is the data race the lesson is about.
+        // Volatile stops the compiler from merging the loop into one add, so every
+        // iteration is a separate load, add, store, just like `mov; add; mov` in assembly
+        unsafe { ptr::write_volatile(addr_of_mut!(RACY_TOTAL), 0) };
+        
+        thread::scope(|scope| {
+            for _ in 0..threads {
+                scope.spawn(|| {
+                    for _ in 0..increments {
+                        unsafe {
+                            let current = ptr::read_volatile(addr_of_mut!(RACY_TOTAL));
+                            ptr::write_volatile(addr_of_mut!(RACY_TOTAL), current + 1);
+                        }
+                        atomic.fetch_add(1, Ordering::Relaxed);  // One indivisible lock xadd
+                    }
+                });
+            }
+        });
+        
+        let report = RaceReport {
+            expected: threads as u64 * increments,
+            racy_total: unsafe { ptr::read_volatile(addr_of_mut!(RACY_TOTAL)) },
+            atomic_total: atomic.load(Ordering::Relaxed),
+        };
+        println!("static mut: {} of {} ({} lost updates), AtomicU64: {}",
+                 report.racy_total, report.expected, report.lost_updates(), report.atomic_total);
+        report
+    }
 }
 
 /// LEVEL 2: C-style thinking (pointers, manual memory)
 mod c_style {
+    use std::alloc::{self, Layout};
     use std::ptr;
     
     pub struct CStyleVector {
@@ -34,61 +102,422 @@ mod c_style {
         
         // Manual memory management like C
         // Prone to leaks, double-free, use-after-free
+        pub fn push(&mut self, value: i32) {
+            if self.len == self.cap {
+                self.grow();
+            }
+            
+            // SAFETY: grow() guarantees len < cap, so ptr + len lies inside the allocation
+            unsafe {
+                self.ptr.add(self.len).write(value);
+            }
+            self.len += 1;
+        }
+        
+        pub fn get(&self, index: usize) -> Option<i32> {
+            if index >= self.len {
+                return None;  // The bounds check C leaves to the caller
+            }
+            
+            // SAFETY: index < len, and every slot below len was written by push()
+            unsafe { Some(self.ptr.add(index).read()) }
+        }
+        
+        pub fn len(&self) -> usize {
+            self.len
+        }
+        
+        /// Doubles the capacity, like realloc-based growth in C
+        fn grow(&mut self) {
+            let new_cap = if self.cap == 0 { 4 } else { self.cap * 2 };
+            let new_layout = Layout::array::<i32>(new_cap).expect("capacity overflow");
+            
+            let new_ptr = if self.cap == 0 {
+                // SAFETY: new_layout has a non-zero size
+                unsafe { alloc::alloc(new_layout) }
+            } else {
+                let old_layout = Layout::array::<i32>(self.cap).unwrap();
+                // SAFETY: ptr was allocated by us with old_layout, and the new size is non-zero
+                unsafe { alloc::realloc(self.ptr.cast(), old_layout, new_layout.size()) }
+            };
+            
+            if new_ptr.is_null() {
+                alloc::handle_alloc_error(new_layout);  // C would hand back NULL and hope you check
+            }
+            self.ptr = new_ptr.cast();
+            self.cap = new_cap;
+        }
+    }
+    
+    impl Drop for CStyleVector {
+        fn drop(&mut self) {
+            if self.cap > 0 {
+                // SAFETY: ptr was allocated with exactly this layout, and Drop runs once,
+                // so there is no double free (i32 needs no per-element drop)
+                unsafe {
+                    alloc::dealloc(self.ptr.cast(), Layout::array::<i32>(self.cap).unwrap());
+                }
+            }
+        }
+    }
+}
+
+/// LEVEL 2 vs LEVEL 3: the same bug in both worlds - forgetting to free memory
+#[cfg(feature = "unsafe-demos")]
+mod leak_contrast {
+    use super::c_style::CStyleVector;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    
+    /// Counts how many times it is dropped, so tests can check cleanup
+    pub struct DropTracker(pub Arc<AtomicUsize>);
+    
+    impl Drop for DropTracker {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+    
+    pub fn c_style_leak() {
+        let mut vector = CStyleVector::new();
+        for i in 0..100 {
+            vector.push(i);
+        }
+        println!("C style: filled a manual buffer with {} ints", vector.len());
+        
+        // The C mistake: return without calling free(). mem::forget skips Drop,
+        // so dealloc never runs and the buffer is lost until the process exits
+        std::mem::forget(vector);
+        println!("C style: forgot to free it - those bytes are leaked");
+    }
+    
+    pub fn rust_style_cleanup(tracker: DropTracker) {
+        let values: Vec<DropTracker> = vec![tracker];
+        println!("Rust style: Vec owns {} tracked value", values.len());
+        
+        // No free() to forget: the owner going out of scope drops the Vec,
+        // which drops each element exactly once
     }
 }
 
 /// LEVEL 3: Rust thinking (ownership, safety, zero-cost)
 mod rust_style {
     use super::*;
+    use std::marker::PhantomData;
     
-    /// Demonstrates software complexity concepts Rust teaches
-    pub struct SafeCounter {
+    /// The general pattern behind SafeCounter: any T, shared and locked
+    pub struct SafeContainer<T> {
         // Arc<Mutex<T>> teaches:
         // - Shared ownership (Arc)
         // - Thread safety (Mutex) 
         // - Type safety (T)
         // - Zero runtime cost over raw pointers
-        inner: Arc<Mutex<u64>>,
+        inner: Arc<Mutex<T>>,
     }
     
-    impl SafeCounter {
-        pub fn new() -> Self {
+    impl<T> SafeContainer<T> {
+        pub fn new(value: T) -> Self {
             Self {
-                inner: Arc::new(Mutex::new(0))
+                inner: Arc::new(Mutex::new(value))
             }
         }
         
-        pub fn increment(&self) -> u64 {
+        pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
             // This simple line teaches:
             // - Lock acquisition
             // - Panic safety (poisoning)
             // - Automatic lock release (RAII)
             // - No data races (compiler guaranteed)
             let mut guard = self.inner.lock().unwrap();
-            *guard += 1;
-            *guard
+            f(&mut guard)
             // Lock automatically released here
         }
         
+        /// Like `with`, but returns None instead of waiting when another thread holds the lock
+        pub fn try_with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+            match self.inner.try_lock() {
+                Ok(mut guard) => Some(f(&mut guard)),
+                Err(TryLockError::WouldBlock) => None,
+                Err(TryLockError::Poisoned(e)) => panic!("lock poisoned: {}", e),
+            }
+        }
+        
+        pub fn get(&self) -> T
+        where
+            T: Clone,
+        {
+            self.with(|value| value.clone())
+        }
+        
+        /// Another handle to the same data - clones the Arc, not the T
+        pub fn clone_handle(&self) -> Self {
+            Self {
+                inner: Arc::clone(&self.inner)
+            }
+        }
+    }
+    
+    /// Demonstrates software complexity concepts Rust teaches
+    pub struct SafeCounter {
+        inner: SafeContainer<u64>,
+    }
+    
+    impl SafeCounter {
+        pub fn new() -> Self {
+            Self {
+                inner: SafeContainer::new(0)
+            }
+        }
+        
+        pub fn increment(&self) -> u64 {
+            self.inner.with(|count| {
+                *count += 1;
+                *count
+            })
+        }
+        
+        /// Teaches: non-blocking synchronization - give up rather than wait on contention
+        pub fn try_increment(&self) -> Option<u64> {
+            self.inner.try_with(|count| {
+                *count += 1;
+                *count
+            })
+        }
+        
+        /// Applies a compound update while holding the lock for its whole duration
+        pub fn update<R>(&self, f: impl FnOnce(&mut u64) -> R) -> R {
+            self.inner.with(f)
+        }
+        
+        pub fn get(&self) -> u64 {
+            self.inner.get()
+        }
+        
+        pub fn decrement(&self) -> u64 {
+            // Teaches: checked arithmetic - saturate at zero instead of wrapping to u64::MAX
+            self.inner.with(|count| {
+                *count = count.saturating_sub(1);
+                *count
+            })
+        }
+        
         pub fn spawn_workers(&self, count: usize) -> Vec<thread::JoinHandle<()>> {
+            self.spawn_workers_with(count, 1000)
+        }
+        
+        /// Spawns the workers, joins every one, and returns the resulting total
+        pub fn run_workers(&self, count: usize, per_worker: usize) -> u64 {
+            for handle in self.spawn_workers_with(count, per_worker) {
+                handle.join().unwrap();
+            }
+            self.get()
+        }
+        
+        pub fn spawn_workers_with(&self, count: usize, per_worker: usize) -> Vec<thread::JoinHandle<()>> {
             // Teaches:
-            // - Clone semantics (Arc::clone)
+            // - Clone semantics (Arc::clone, behind clone_handle)
             // - Move semantics (move closure)
             // - Thread ownership transfer
             // - Join handle responsibility
+            (0..count).map(|i| {
+                let counter = self.inner.clone_handle();
+                thread::spawn(move || {
+                    for _ in 0..per_worker {
+                        counter.with(|count| *count += 1);
+                    }
+                    println!("Worker {} completed", i);
+                })
+            }).collect()
+        }
+    }
+    
+    /// The lock-free alternative to SafeCounter.
+    /// Prefer it when the shared state is a single integer: every increment is one
+    /// atomic CPU instruction, with no lock to contend on and no poisoning to handle.
+    /// Prefer SafeCounter (Mutex) once an update touches more than one value or needs
+    /// an invariant held across several steps - atomics can't make those atomic together.
+    pub struct AtomicCounter {
+        inner: Arc<AtomicU64>,
+    }
+    
+    impl AtomicCounter {
+        pub fn new() -> Self {
+            Self {
+                inner: Arc::new(AtomicU64::new(0))
+            }
+        }
+        
+        pub fn increment(&self) -> u64 {
+            // fetch_add returns the previous value; Relaxed suffices because
+            // no other memory is published through the counter
+            self.inner.fetch_add(1, Ordering::Relaxed) + 1
+        }
+        
+        pub fn get(&self) -> u64 {
+            self.inner.load(Ordering::Relaxed)
+        }
+        
+        pub fn spawn_workers(&self, count: usize) -> Vec<thread::JoinHandle<()>> {
             (0..count).map(|i| {
                 let counter = Arc::clone(&self.inner);
                 thread::spawn(move || {
                     for _ in 0..1000 {
-                        let mut guard = counter.lock().unwrap();
-                        *guard += 1;
+                        counter.fetch_add(1, Ordering::Relaxed);
                     }
-                    println!("Worker {} completed", i);
+                    println!("Atomic worker {} completed", i);
                 })
             }).collect()
         }
     }
     
+    /// Teaches: Send is a compile-time contract. Rc's reference count is a plain
+    /// integer, so two threads cloning the same Rc would race on it; the compiler
+    /// refuses to move an Rc (or anything containing one) across threads:
+    ///
+    /// ```compile_fail,E0277
+    /// use std::rc::Rc;
+    /// use std::thread;
+    ///
+    /// struct LocalCount {
+    ///     count: Rc<u64>,
+    /// }
+    ///
+    /// let local = LocalCount { count: Rc::new(42) };
+    /// // error[E0277]: `Rc<u64>` cannot be sent between threads safely
+    /// thread::spawn(move || println!("{}", local.count));
+    /// ```
+    pub struct LocalCount {
+        pub count: Rc<u64>,
+    }
+    
+    /// The thread-safe twin of LocalCount: Arc updates its count atomically,
+    /// so it is Send + Sync and the same spawn compiles
+    pub struct SharedCount {
+        pub count: Arc<u64>,
+    }
+    
+    impl SharedCount {
+        /// Hands a clone to another thread, which reads it and hands back the value
+        pub fn read_on_thread(&self) -> u64 {
+            let count = Arc::clone(&self.count);
+            thread::spawn(move || *count).join().unwrap()
+        }
+    }
+    
+    /// The reader-writer middle ground: any number of readers at once, writers alone.
+    /// Pays off when reads vastly outnumber writes; for write-heavy counters the
+    /// extra bookkeeping makes it slower than a plain Mutex
+    #[derive(Clone)]
+    pub struct RwCounter {
+        inner: Arc<RwLock<u64>>,
+    }
+    
+    impl RwCounter {
+        pub fn new() -> Self {
+            Self {
+                inner: Arc::new(RwLock::new(0))
+            }
+        }
+        
+        pub fn increment(&self) -> u64 {
+            // The write lock waits for every reader to leave, then excludes everyone
+            let mut guard = self.inner.write().unwrap();
+            *guard += 1;
+            *guard
+        }
+        
+        /// Takes the read lock `times` times, returning the value seen each time
+        pub fn read_many(&self, times: usize) -> Vec<u64> {
+            (0..times).map(|_| *self.inner.read().unwrap()).collect()
+        }
+        
+        /// Runs f while holding a read lock; other readers may hold one at the same time
+        pub fn with_read<R>(&self, f: impl FnOnce(u64) -> R) -> R {
+            let guard = self.inner.read().unwrap();
+            f(*guard)
+        }
+    }
+    
+    /// Teaches: const generics - the shard count N is part of the type, so the array
+    /// lives inline with no heap allocation and `ShardedCounter<8>` differs from `<16>`.
+    /// Striping spreads contended increments over N atomics; production versions
+    /// also pad each shard to its own cache line to avoid false sharing
+    pub struct ShardedCounter<const N: usize> {
+        shards: [AtomicU64; N],
+    }
+    
+    impl<const N: usize> ShardedCounter<N> {
+        pub fn new() -> Self {
+            Self {
+                shards: std::array::from_fn(|_| AtomicU64::new(0))
+            }
+        }
+        
+        pub fn increment(&self) {
+            // Each thread gets a stable index the first time it increments
+            static NEXT_THREAD: AtomicUsize = AtomicUsize::new(0);
+            thread_local! {
+                static THREAD_INDEX: usize = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+            }
+            
+            let shard = THREAD_INDEX.with(|index| index % N);
+            self.shards[shard].fetch_add(1, Ordering::Relaxed);
+        }
+        
+        /// Sums the shards; exact once the incrementing threads have been joined
+        pub fn total(&self) -> u64 {
+            self.shards.iter().map(|shard| shard.load(Ordering::Relaxed)).sum()
+        }
+    }
+    
+    /// Unit markers for Measurement: never constructed, they only exist in types
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Meters;
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Feet;
+    
+    /// Teaches: Phantom types - the unit lives only in the type, so
+    /// Measurement<Meters> is a bare f64 at runtime yet can't be mixed with feet
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub struct Measurement<Unit> {
+        pub value: f64,
+        unit: PhantomData<Unit>,
+    }
+    
+    impl<Unit> Measurement<Unit> {
+        pub fn new(value: f64) -> Self {
+            Self { value, unit: PhantomData }
+        }
+    }
+    
+    // Same unit in, same unit out; Meters + Feet has no impl, so it doesn't compile
+    impl<Unit> std::ops::Add for Measurement<Unit> {
+        type Output = Measurement<Unit>;
+        
+        fn add(self, other: Self) -> Self::Output {
+            Measurement::new(self.value + other.value)
+        }
+    }
+    
+    /// Unit conversions are explicit, and the only way to change the type
+    pub trait Convert<To> {
+        fn convert(self) -> Measurement<To>;
+    }
+    
+    const FEET_PER_METER: f64 = 3.28084;
+    
+    impl Convert<Feet> for Measurement<Meters> {
+        fn convert(self) -> Measurement<Feet> {
+            Measurement::new(self.value * FEET_PER_METER)
+        }
+    }
+    
+    impl Convert<Meters> for Measurement<Feet> {
+        fn convert(self) -> Measurement<Meters> {
+            Measurement::new(self.value / FEET_PER_METER)
+        }
+    }
+    
     /// Demonstrates type-level software architecture
     #[derive(Debug)]
     pub enum ProcessResult<T> {
@@ -121,6 +550,149 @@ mod rust_style {
                 ProcessResult::Fatal(msg) => ProcessResult::Fatal(msg),
             }
         }
+        
+        /// Teaches: Rewriting one failure kind while leaving the others untouched
+        pub fn map_retry<F>(self, f: F) -> ProcessResult<T>
+        where
+            F: FnOnce(String) -> String,
+        {
+            match self {
+                ProcessResult::Retry(msg) => ProcessResult::Retry(f(msg)),
+                other => other,
+            }
+        }
+        
+        pub fn map_fatal<F>(self, f: F) -> ProcessResult<T>
+        where
+            F: FnOnce(String) -> String,
+        {
+            match self {
+                ProcessResult::Fatal(msg) => ProcessResult::Fatal(f(msg)),
+                other => other,
+            }
+        }
+        
+        /// Teaches: Extracting a value with a fallback instead of matching every variant
+        pub fn unwrap_or(self, default: T) -> T {
+            match self {
+                ProcessResult::Success(value) => value,
+                _ => default,
+            }
+        }
+        
+        /// The fallback is computed lazily from the Retry or Fatal message
+        pub fn unwrap_or_else<F>(self, f: F) -> T
+        where
+            F: FnOnce(String) -> T,
+        {
+            match self {
+                ProcessResult::Success(value) => value,
+                ProcessResult::Retry(msg) | ProcessResult::Fatal(msg) => f(msg),
+            }
+        }
+        
+        pub fn is_success(&self) -> bool {
+            matches!(self, ProcessResult::Success(_))
+        }
+        
+        pub fn ok(self) -> Option<T> {
+            match self {
+                ProcessResult::Success(value) => Some(value),
+                _ => None,
+            }
+        }
+        
+        /// Borrows the success value, if any, as a zero-or-one element iterator
+        pub fn iter(&self) -> impl Iterator<Item = &T> {
+            match self {
+                ProcessResult::Success(value) => Some(value),
+                _ => None,
+            }.into_iter()
+        }
+        
+        /// The failure, if there was one, as a real error type
+        pub fn err(self) -> Option<ProcessError> {
+            self.into_result().err()
+        }
+        
+        /// Teaches: Bridging to std Result so `?` works, without losing the failure kind
+        pub fn into_result(self) -> Result<T, ProcessError> {
+            match self {
+                ProcessResult::Success(value) => Ok(value),
+                ProcessResult::Retry(msg) => Err(ProcessError::Retry(msg)),
+                ProcessResult::Fatal(msg) => Err(ProcessError::Fatal(msg)),
+            }
+        }
+    }
+    
+    /// The failure half of ProcessResult, as a standalone error type
+    #[derive(Debug, PartialEq)]
+    pub enum ProcessError {
+        Retry(String),
+        Fatal(String),
+    }
+    
+    impl fmt::Display for ProcessError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                ProcessError::Retry(msg) => write!(f, "transient failure, retry later: {}", msg),
+                ProcessError::Fatal(msg) => write!(f, "fatal failure: {}", msg),
+            }
+        }
+    }
+    
+    /// Teaches: Implementing Error lets ProcessError travel through Box<dyn Error> and `?`
+    impl std::error::Error for ProcessError {}
+    
+    /// Teaches: Composing with iterator chains like Option does -
+    /// `results.into_iter().flatten()` keeps successes and drops failures
+    impl<T> IntoIterator for ProcessResult<T> {
+        type Item = T;
+        type IntoIter = std::option::IntoIter<T>;
+        
+        fn into_iter(self) -> Self::IntoIter {
+            self.ok().into_iter()
+        }
+    }
+    
+    /// Flattens both failure kinds into a prefixed message
+    impl<T> From<ProcessResult<T>> for Result<T, String> {
+        fn from(result: ProcessResult<T>) -> Self {
+            match result {
+                ProcessResult::Success(value) => Ok(value),
+                ProcessResult::Retry(msg) => Err(format!("retry: {}", msg)),
+                ProcessResult::Fatal(msg) => Err(format!("fatal: {}", msg)),
+            }
+        }
+    }
+    
+    /// Teaches: Retry as a first-class outcome - re-run with exponential backoff,
+    /// give up immediately on Fatal, and hand back the last Retry once attempts run out
+    pub fn run_with_retry<T, F>(max_attempts: usize, base_delay: Duration, f: F) -> ProcessResult<T>
+    where
+        F: Fn() -> ProcessResult<T>,
+    {
+        run_with_retry_counted(max_attempts, base_delay, f).0
+    }
+    
+    /// run_with_retry that also reports how many attempts it made
+    pub fn run_with_retry_counted<T, F>(max_attempts: usize, base_delay: Duration, f: F) -> (ProcessResult<T>, usize)
+    where
+        F: Fn() -> ProcessResult<T>,
+    {
+        let mut delay = base_delay;
+        let mut attempt = 1;
+        
+        loop {
+            match f() {
+                ProcessResult::Retry(_) if attempt < max_attempts => {
+                    thread::sleep(delay);
+                    delay *= 2;
+                    attempt += 1;
+                }
+                outcome => return (outcome, attempt),
+            }
+        }
     }
     
     /// Demonstrates zero-cost abstractions
@@ -131,7 +703,26 @@ mod rust_style {
         fn process(&self, input: Self::Input) -> ProcessResult<Self::Output>;
     }
     
-    pub struct DataProcessor;
+    /// Keeps values >= min_value, scales them by multiplier, and asks for a retry
+    /// when the sum exceeds retry_threshold (a sum equal to the threshold succeeds)
+    pub struct DataProcessor {
+        pub min_value: i32,
+        pub multiplier: i32,
+        pub retry_threshold: i32,
+    }
+    
+    impl DataProcessor {
+        pub fn new(min_value: i32, multiplier: i32, retry_threshold: i32) -> Self {
+            Self { min_value, multiplier, retry_threshold }
+        }
+    }
+    
+    impl Default for DataProcessor {
+        /// Positive values only, doubled, retrying above 1000
+        fn default() -> Self {
+            Self::new(1, 2, 1000)
+        }
+    }
     
     impl Processor for DataProcessor {
         type Input = Vec<i32>;
@@ -146,62 +737,260 @@ mod rust_style {
             // but teaches high-level thinking patterns
             let sum: i32 = input
                 .iter()
-                .filter(|&&x| x > 0)  // Functional programming
-                .map(|&x| x * 2)      // Transformation pipeline
-                .sum();               // Reduction
+                .filter(|&&x| x >= self.min_value)  // Functional programming
+                .map(|&x| x * self.multiplier)      // Transformation pipeline
+                .sum();                             // Reduction
             
-            if sum > 1000 {
+            if sum > self.retry_threshold {
                 ProcessResult::Retry("Sum too large".to_string())
             } else {
                 ProcessResult::Success(sum)
             }
         }
     }
+    
+    /// A second Processor: proves the trait abstracts over more than one input type
+    pub struct StringProcessor;
+    
+    impl Processor for StringProcessor {
+        type Input = Vec<String>;
+        type Output = usize;
+        
+        fn process(&self, input: Vec<String>) -> ProcessResult<usize> {
+            if input.is_empty() {
+                return ProcessResult::Fatal("Empty input".to_string());
+            }
+            
+            ProcessResult::Success(input.iter().map(|s| s.chars().count()).sum())
+        }
+    }
+    
+    /// Two processors run back to back; built with `chain`
+    pub struct Chained<A, B> {
+        first: A,
+        second: B,
+    }
+    
+    /// Teaches: Type-safe pipelines - `b` must accept exactly what `a` produces,
+    /// so a mismatched stage is a compile error rather than a runtime one
+    pub fn chain<A, B>(a: A, b: B) -> Chained<A, B>
+    where
+        A: Processor,
+        B: Processor<Input = A::Output>,
+    {
+        Chained { first: a, second: b }
+    }
+    
+    impl<A, B> Processor for Chained<A, B>
+    where
+        A: Processor,
+        B: Processor<Input = A::Output>,
+    {
+        type Input = A::Input;
+        type Output = B::Output;
+        
+        fn process(&self, input: A::Input) -> ProcessResult<B::Output> {
+            // and_then short-circuits: Retry or Fatal from the first stage skips the second
+            self.first.process(input).and_then(|value| self.second.process(value))
+        }
+    }
+    
+    /// Teaches: Static dispatch - monomorphized once per processor, no vtable
+    pub fn run_processor<P: Processor>(processor: &P, input: P::Input) -> ProcessResult<P::Output> {
+        processor.process(input)
+    }
 }
 
 /// LEVEL 4: Application-level thinking (business logic)
 mod application_level {
     use super::rust_style::*;
+    use std::marker::PhantomData;
+    use std::time::Duration;
+    
+    const DEFAULT_MAX_ATTEMPTS: usize = 3;
+    const RETRY_BASE_DELAY: Duration = Duration::from_millis(10);
     
     /// Demonstrates how Rust's concepts scale to application architecture
-    pub struct Application {
+    pub struct Application<P = DataProcessor> {
         counter: SafeCounter,
-        processor: DataProcessor,
+        processor: P,
+        workers: usize,
+        worker_iterations: usize,
+        max_attempts: usize,
     }
     
     impl Application {
         pub fn new() -> Self {
-            Self {
-                counter: SafeCounter::new(),
-                processor: DataProcessor,
-            }
+            ApplicationBuilder::new()
+                .workers(4)
+                .worker_iterations(1000)
+                .processor(DataProcessor::default())
+                .build()
         }
-        
+    }
+    
+    impl<P: Processor<Input = Vec<i32>, Output = i32>> Application<P> {
         /// Orchestrates complex operations safely
-        pub fn run_simulation(&self) -> ProcessResult<String> {
+        pub fn run_simulation(&self) -> Result<String, ProcessError> {
             // Spawn concurrent workers
-            let handles = self.counter.spawn_workers(4);
+            let handles = self.counter.spawn_workers_with(self.workers, self.worker_iterations);
             
-            // Process data
-            let data = vec![1, 2, 3, 4, 5];
-            let result = match self.processor.process(data) {
-                ProcessResult::Success(val) => val,
-                ProcessResult::Retry(msg) => return ProcessResult::Retry(msg),
-                ProcessResult::Fatal(msg) => return ProcessResult::Fatal(msg),
-            };
+            // Process data, re-attempting Retry with a fresh input and growing backoff;
+            // Fatal, or a Retry on the last attempt, bubbles out as the error
+            let (outcome, attempts) = run_with_retry_counted(self.max_attempts, RETRY_BASE_DELAY, || {
+                self.processor.process(vec![1, 2, 3, 4, 5])
+            });
+            println!("Processing finished after {} of {} attempt(s)", attempts, self.max_attempts);
+            let result = outcome.into_result()?;
             
             // Wait for workers
             for handle in handles {
                 handle.join().unwrap();
             }
             
-            ProcessResult::Success(format!(
+            Ok(format!(
                 "Simulation completed. Result: {}, Counter: {}", 
                 result,
-                self.counter.increment()
+                self.counter.get()
             ))
         }
     }
+    
+    /// Typestate markers: whether a required builder field has been set yet
+    pub struct Missing;
+    pub struct Set;
+    
+    /// Teaches: Typestate - the type records which fields are set, so calling
+    /// `build()` before all of them are is a compile error, not a runtime panic.
+    /// The processor slot is its own state: `Missing` until `.processor(p)` stores one.
+    pub struct ApplicationBuilder<Workers, Iterations, Proc> {
+        workers: usize,
+        worker_iterations: usize,
+        processor: Proc,
+        max_attempts: usize,
+        _state: PhantomData<(Workers, Iterations)>,
+    }
+    
+    impl ApplicationBuilder<Missing, Missing, Missing> {
+        pub fn new() -> Self {
+            Self {
+                workers: 0,
+                worker_iterations: 0,
+                processor: Missing,
+                max_attempts: DEFAULT_MAX_ATTEMPTS,
+                _state: PhantomData,
+            }
+        }
+    }
+    
+    impl<W, I, P> ApplicationBuilder<W, I, P> {
+        pub fn workers(self, workers: usize) -> ApplicationBuilder<Set, I, P> {
+            ApplicationBuilder {
+                workers,
+                worker_iterations: self.worker_iterations,
+                processor: self.processor,
+                max_attempts: self.max_attempts,
+                _state: PhantomData,
+            }
+        }
+        
+        pub fn worker_iterations(self, worker_iterations: usize) -> ApplicationBuilder<W, Set, P> {
+            ApplicationBuilder {
+                workers: self.workers,
+                worker_iterations,
+                processor: self.processor,
+                max_attempts: self.max_attempts,
+                _state: PhantomData,
+            }
+        }
+        
+        pub fn processor<Q>(self, processor: Q) -> ApplicationBuilder<W, I, Q>
+        where
+            Q: Processor<Input = Vec<i32>, Output = i32>,
+        {
+            ApplicationBuilder {
+                workers: self.workers,
+                worker_iterations: self.worker_iterations,
+                processor,
+                max_attempts: self.max_attempts,
+                _state: PhantomData,
+            }
+        }
+        
+        /// Optional: how many times processing may run before a Retry becomes the error (default 3)
+        pub fn max_attempts(self, max_attempts: usize) -> Self {
+            Self { max_attempts, ..self }
+        }
+    }
+    
+    // build() exists only for the fully configured state; Missing isn't a Processor
+    impl<P: Processor<Input = Vec<i32>, Output = i32>> ApplicationBuilder<Set, Set, P> {
+        pub fn build(self) -> Application<P> {
+            Application {
+                counter: SafeCounter::new(),
+                processor: self.processor,
+                workers: self.workers,
+                worker_iterations: self.worker_iterations,
+                max_attempts: self.max_attempts,
+            }
+        }
+    }
+}
+
+/// Nanoseconds per increment at each level, plus the counts they reached
+#[derive(Debug)]
+pub struct LevelTimings {
+    pub unsafe_ns: f64,
+    pub atomic_ns: f64,
+    pub mutex_ns: f64,
+    pub unsafe_count: u64,
+    pub atomic_count: u64,
+    pub mutex_count: u64,
+}
+
+/// The cost of safety: the same single-threaded increments through each level's counter.
+/// Uncontended, the atomic and the mutex add only nanoseconds per operation over the raw static
+pub fn bench_levels(iterations: usize) -> LevelTimings {
+    use std::hint::black_box;
+    use std::time::Instant;
+    
+    let ns_per_op = |start: Instant| start.elapsed().as_nanos() as f64 / iterations.max(1) as f64;
+    
+    // unsafe_counter never resets, so count how far it moved
+    let _serial = assembly_style::serial_guard();
+    let before = assembly_style::unsafe_counter();
+    let start = Instant::now();
+    let mut last = before;
+    for _ in 0..iterations {
+        last = black_box(assembly_style::unsafe_counter());
+    }
+    let unsafe_ns = ns_per_op(start);
+    
+    let atomic = rust_style::AtomicCounter::new();
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(atomic.increment());
+    }
+    let atomic_ns = ns_per_op(start);
+    
+    let mutex = rust_style::SafeCounter::new();
+    let start = Instant::now();
+    for _ in 0..iterations {
+        black_box(mutex.increment());
+    }
+    let mutex_ns = ns_per_op(start);
+    
+    let timings = LevelTimings {
+        unsafe_ns,
+        atomic_ns,
+        mutex_ns,
+        unsafe_count: last - before,
+        atomic_count: atomic.get(),
+        mutex_count: mutex.get(),
+    };
+    println!("static mut: {:.2} ns/op, AtomicU64: {:.2} ns/op, Mutex: {:.2} ns/op",
+             timings.unsafe_ns, timings.atomic_ns, timings.mutex_ns);
+    timings
 }
 
 #[cfg(test)]
@@ -213,18 +1002,466 @@ mod educational_tests {
         println!("\n=== Educational Progression ===");
         
         // Level 1: Assembly-style (unsafe, manual)
+        let serial = assembly_style::serial_guard();
         println!("Assembly style: {}", assembly_style::unsafe_counter());
+        drop(serial);
         
         // Level 3: Rust-style (safe, expressive)
         let counter = rust_style::SafeCounter::new();
         println!("Rust style: {}", counter.increment());
         
+        // Level 3, lock-free: same workload, same total, no Mutex
+        let locked = rust_style::SafeCounter::new();
+        let atomic = rust_style::AtomicCounter::new();
+        for handle in locked.spawn_workers(4).into_iter().chain(atomic.spawn_workers(4)) {
+            handle.join().unwrap();
+        }
+        println!("Mutex total: {}, atomic total: {}", locked.get(), atomic.get());
+        assert_eq!(locked.get(), atomic.get());
+        
         // Level 4: Application-level
         let app = application_level::Application::new();
-        match app.run_simulation() {
-            rust_style::ProcessResult::Success(msg) => println!("App level: {}", msg),
-            _ => println!("App level: Error occurred"),
+        let summary = app.run_simulation().unwrap_or_else(|e| format!("Error occurred: {}", e));
+        println!("App level: {}", summary);
+    }
+    
+    #[test]
+    #[cfg_attr(miri, ignore = "the static mut data race is deliberate, and Miri aborts on it")]
+    fn atomic_total_is_exact_where_static_mut_races() {
+        // race_comparison serializes itself on a lock, so this is safe to run alongside other tests
+        let report = assembly_style::race_comparison(8, 100_000);
+        assert_eq!(report.atomic_total, report.expected);
+        
+        // The racy total is unreliable: usually short, but it may happen to come out exact,
+        // so the only thing we can assert is that races lose updates rather than invent them
+        assert!(report.racy_total <= report.expected);
+    }
+    
+    #[test]
+    fn safe_counter_operations_compose() {
+        let counter = rust_style::SafeCounter::new();
+        counter.increment();
+        counter.increment();
+        counter.increment();
+        assert_eq!(counter.decrement(), 2);
+        assert_eq!(counter.get(), 2);
+        
+        // get() only reads, and decrement() stops at zero
+        assert_eq!(counter.get(), 2);
+        counter.decrement();
+        counter.decrement();
+        assert_eq!(counter.decrement(), 0);
+        assert_eq!(counter.get(), 0);
+    }
+    
+    #[test]
+    fn safe_container_shared_across_threads() {
+        let shared = rust_style::SafeContainer::new(Vec::<i32>::new());
+        let handles: Vec<_> = (0..8).map(|i| {
+            let handle = shared.clone_handle();
+            thread::spawn(move || {
+                for j in 0..100 {
+                    handle.with(|values| values.push(i * 100 + j));
+                }
+            })
+        }).collect();
+        for handle in handles {
+            handle.join().unwrap();
         }
+        
+        assert_eq!(shared.with(|values| values.len()), 800);
+        assert_eq!(shared.get().len(), 800);
+    }
+    
+    #[test]
+    fn try_increment_gives_up_on_contention() {
+        use std::sync::mpsc;
+        
+        let counter = rust_style::SafeCounter::new();
+        let (locked_tx, locked_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        
+        let counter = &counter;
+        thread::scope(|scope| {
+            // Hold the lock until the main thread has tried its increment
+            scope.spawn(move || counter.update(|_| {
+                locked_tx.send(()).unwrap();
+                release_rx.recv().unwrap();
+            }));
+            
+            locked_rx.recv().unwrap();
+            assert_eq!(counter.try_increment(), None);
+            release_tx.send(()).unwrap();
+        });
+        
+        assert_eq!(counter.try_increment(), Some(1));
+    }
+    
+    #[test]
+    fn run_workers_returns_joined_total() {
+        let counter = rust_style::SafeCounter::new();
+        assert_eq!(counter.run_workers(8, 500), 4000);
+    }
+    
+    #[test]
+    fn atomic_counter_workers_total() {
+        let counter = rust_style::AtomicCounter::new();
+        for handle in counter.spawn_workers(4) {
+            handle.join().unwrap();
+        }
+        assert_eq!(counter.get(), 4 * 1000);
+        assert_eq!(counter.increment(), 4001);
+    }
+    
+    #[test]
+    fn sharded_counter_total_under_contention() {
+        let counter = rust_style::ShardedCounter::<8>::new();
+        thread::scope(|scope| {
+            for _ in 0..16 {
+                scope.spawn(|| {
+                    for _ in 0..10_000 {
+                        counter.increment();
+                    }
+                });
+            }
+        });
+        assert_eq!(counter.total(), 16 * 10_000);
+    }
+    
+    #[test]
+    fn rw_counter_readers_share_writers_serialize() {
+        use std::sync::Barrier;
+        
+        let counter = rust_style::RwCounter::new();
+        
+        // Both readers must be inside the read lock to pass the barrier,
+        // so this would deadlock if readers blocked each other
+        let inside = Barrier::new(2);
+        thread::scope(|scope| {
+            for _ in 0..2 {
+                scope.spawn(|| counter.with_read(|_| { inside.wait(); }));
+            }
+        });
+        
+        // Writers serialize (no lost increments) while readers only ever see
+        // whole values that never go backwards
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                let writer = counter.clone();
+                scope.spawn(move || {
+                    for _ in 0..1000 {
+                        writer.increment();
+                    }
+                });
+            }
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    let seen = counter.read_many(1000);
+                    assert!(seen.windows(2).all(|pair| pair[0] <= pair[1]));
+                    assert!(seen.iter().all(|&value| value <= 4000));
+                });
+            }
+        });
+        
+        assert_eq!(counter.read_many(3), vec![4000; 3]);
+    }
+    
+    #[test]
+    fn arc_crosses_threads_where_rc_cannot() {
+        // LocalCount stays on its own thread; its compile_fail doctest shows the rejected spawn
+        let local = rust_style::LocalCount { count: Rc::new(42) };
+        assert_eq!(*local.count, 42);
+        
+        let shared = rust_style::SharedCount { count: Arc::new(42) };
+        assert_eq!(shared.read_on_thread(), 42);
+        assert_eq!(Arc::strong_count(&shared.count), 1);  // The thread's clone was dropped
+    }
+    
+    #[test]
+    fn c_style_vector_round_trip() {
+        // Exercises every unsafe path (alloc, realloc, dealloc); run
+        // `cargo +nightly miri test c_style_vector_round_trip` to have Miri check it for undefined behavior
+        let mut vector = c_style::CStyleVector::new();
+        for i in 0..1000 {
+            vector.push(i);
+        }
+        
+        assert_eq!(vector.len(), 1000);
+        assert!((0..1000).all(|i| vector.get(i as usize) == Some(i)));
+        assert_eq!(vector.get(1000), None);
+    }
+    
+    #[cfg(feature = "unsafe-demos")]
+    #[test]
+    fn leak_contrast_safe_path_drops_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        
+        leak_contrast::c_style_leak();
+        
+        let drops = Arc::new(AtomicUsize::new(0));
+        leak_contrast::rust_style_cleanup(leak_contrast::DropTracker(Arc::clone(&drops)));
+        assert_eq!(drops.load(Ordering::SeqCst), 1);
+    }
+    
+    #[test]
+    fn process_result_converts_to_std_result() {
+        use rust_style::{ProcessError, ProcessResult};
+        
+        assert_eq!(Result::<i32, String>::from(ProcessResult::Success(7)), Ok(7));
+        assert_eq!(Result::<i32, String>::from(ProcessResult::Retry("busy".to_string())), Err("retry: busy".to_string()));
+        assert_eq!(Result::<i32, String>::from(ProcessResult::Fatal("gone".to_string())), Err("fatal: gone".to_string()));
+        
+        assert_eq!(ProcessResult::Success(7).into_result(), Ok(7));
+        assert_eq!(ProcessResult::<i32>::Retry("busy".to_string()).into_result(), Err(ProcessError::Retry("busy".to_string())));
+        assert_eq!(ProcessResult::<i32>::Fatal("gone".to_string()).into_result(), Err(ProcessError::Fatal("gone".to_string())));
+        
+        // Each combinator only touches its own variant
+        let retry = ProcessResult::<i32>::Retry("busy".to_string()).map_retry(|m| m + " (attempt 2)").map_fatal(|_| unreachable!());
+        assert_eq!(retry.into_result(), Err(ProcessError::Retry("busy (attempt 2)".to_string())));
+        let fatal = ProcessResult::<i32>::Fatal("gone".to_string()).map_retry(|_| unreachable!()).map_fatal(|m| m.to_uppercase());
+        assert_eq!(fatal.into_result(), Err(ProcessError::Fatal("GONE".to_string())));
+        assert_eq!(ProcessResult::Success(7).map_retry(|_| unreachable!()).map_fatal(|_| unreachable!()).into_result(), Ok(7));
+    }
+    
+    #[test]
+    fn process_result_extractors() {
+        use rust_style::ProcessResult;
+        
+        assert_eq!(ProcessResult::Retry("x".to_string()).unwrap_or(0), 0);
+        assert_eq!(ProcessResult::Success(5).unwrap_or(0), 5);
+        assert_eq!(ProcessResult::Fatal("boom".to_string()).unwrap_or_else(|msg| msg.len()), 4);
+        
+        assert_eq!(ProcessResult::Success(5).ok(), Some(5));
+        assert_eq!(ProcessResult::<i32>::Fatal("boom".to_string()).ok(), None);
+        assert!(ProcessResult::Success(()).is_success());
+        assert!(!ProcessResult::<()>::Retry("x".to_string()).is_success());
+    }
+    
+    #[test]
+    fn data_processor_threshold_is_inclusive() {
+        use rust_style::{DataProcessor, ProcessResult, Processor};
+        
+        // 5 is filtered out; (10 + 20) * 3 = 90
+        let processor = DataProcessor::new(10, 3, 90);
+        assert!(matches!(processor.process(vec![5, 10, 20]), ProcessResult::Success(90)));
+        
+        // One past the threshold asks for a retry
+        let processor = DataProcessor::new(10, 3, 89);
+        assert!(matches!(processor.process(vec![5, 10, 20]), ProcessResult::Retry(_)));
+        
+        // The default keeps the original policy: positives doubled, retry above 1000
+        let processor = DataProcessor::default();
+        assert!(matches!(processor.process(vec![-3, 0, 500]), ProcessResult::Success(1000)));
+        assert!(matches!(processor.process(vec![501]), ProcessResult::Retry(_)));
+    }
+    
+    #[test]
+    fn run_processor_is_generic_over_processors() {
+        use rust_style::{run_processor, DataProcessor, ProcessResult, StringProcessor};
+        
+        let numbers = run_processor(&DataProcessor::default(), vec![1, 2, 3]);
+        assert!(matches!(numbers, ProcessResult::Success(12)));
+        
+        let words = vec!["ferris".to_string(), "🦀".to_string()];
+        assert!(matches!(run_processor(&StringProcessor, words), ProcessResult::Success(7)));
+        assert!(matches!(run_processor(&StringProcessor, Vec::new()), ProcessResult::Fatal(_)));
+    }
+    
+    #[test]
+    fn chained_processors_short_circuit() {
+        use rust_style::{chain, run_processor, ProcessResult, Processor};
+        
+        struct Doubler;
+        impl Processor for Doubler {
+            type Input = i32;
+            type Output = i32;
+            
+            fn process(&self, input: i32) -> ProcessResult<i32> {
+                match input.checked_mul(2) {
+                    Some(doubled) => ProcessResult::Success(doubled),
+                    None => ProcessResult::Fatal("overflow".to_string()),
+                }
+            }
+        }
+        
+        struct EvenCheck;
+        impl Processor for EvenCheck {
+            type Input = i32;
+            type Output = bool;
+            
+            fn process(&self, input: i32) -> ProcessResult<bool> {
+                ProcessResult::Success(input % 2 == 0)
+            }
+        }
+        
+        let pipeline = chain(Doubler, EvenCheck);
+        assert!(matches!(run_processor(&pipeline, 21), ProcessResult::Success(true)));
+        assert!(matches!(pipeline.process(i32::MAX), ProcessResult::Fatal(_)));
+    }
+    
+    #[test]
+    fn process_error_display_keeps_message() {
+        use rust_style::ProcessResult;
+        
+        let retry = ProcessResult::<i32>::Retry("queue full".to_string()).err().unwrap();
+        assert!(retry.to_string().contains("queue full"));
+        
+        let fatal: Box<dyn std::error::Error> = Box::new(ProcessResult::<i32>::Fatal("disk gone".to_string()).err().unwrap());
+        assert!(fatal.to_string().contains("disk gone"));
+        
+        assert!(ProcessResult::Success(1).err().is_none());
+    }
+    
+    #[test]
+    fn simulation_reports_worker_total() {
+        let app = application_level::Application::new();
+        let summary = app.run_simulation().unwrap();
+        
+        // 4 workers x 1000 increments, with no extra increment from reporting
+        assert!(summary.ends_with(&format!("Counter: {}", 4 * 1000)), "{}", summary);
+    }
+    
+    #[test]
+    fn builder_configures_application() {
+        use application_level::ApplicationBuilder;
+        use rust_style::DataProcessor;
+        
+        let app = ApplicationBuilder::new()
+            .processor(DataProcessor::new(1, 1, 100))
+            .workers(3)
+            .worker_iterations(250)
+            .build();
+        assert_eq!(app.run_simulation().unwrap(), "Simulation completed. Result: 15, Counter: 750");
+        
+        // Leaving out a required field doesn't compile, e.g.
+        //     ApplicationBuilder::new().workers(3).processor(DataProcessor::default()).build();
+        // fails with "no method named `build` found for struct
+        // `ApplicationBuilder<Set, Missing, DataProcessor>`"
+    }
+    
+    #[test]
+    fn bench_levels_counts_and_times_each_level() {
+        let timings = bench_levels(10_000);
+        
+        assert_eq!(timings.unsafe_count, 10_000);
+        assert_eq!(timings.atomic_count, 10_000);
+        assert_eq!(timings.mutex_count, 10_000);
+        for ns in [timings.unsafe_ns, timings.atomic_ns, timings.mutex_ns] {
+            assert!(ns > 0.0 && ns.is_finite());
+        }
+    }
+    
+    #[test]
+    fn process_results_flatten_to_successes() {
+        use rust_style::ProcessResult;
+        
+        let results = vec![
+            ProcessResult::Success(1),
+            ProcessResult::Retry("busy".to_string()),
+            ProcessResult::Success(2),
+            ProcessResult::Fatal("broken".to_string()),
+            ProcessResult::Success(3),
+        ];
+        
+        let borrowed: Vec<&i32> = results.iter().flat_map(ProcessResult::iter).collect();
+        assert_eq!(borrowed, vec![&1, &2, &3]);
+        
+        let values: Vec<i32> = results.into_iter().flatten().collect();
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+    
+    #[test]
+    fn phantom_units_add_and_convert() {
+        use rust_style::{Convert, Feet, Measurement, Meters};
+        
+        let total = Measurement::<Meters>::new(2.0) + Measurement::new(3.0);
+        assert_eq!(total.value, 5.0);
+        
+        let feet: Measurement<Feet> = total.convert();
+        assert!((feet.value - 16.4042).abs() < 1e-9);
+        let back: Measurement<Meters> = feet.convert();
+        assert!((back.value - 5.0).abs() < 1e-9);
+        
+        // The unit costs nothing at runtime
+        assert_eq!(std::mem::size_of::<Measurement<Meters>>(), std::mem::size_of::<f64>());
+        
+        // Mixing units doesn't compile:
+        //     Measurement::<Meters>::new(1.0) + Measurement::<Feet>::new(1.0);
+        // fails with E0308 "mismatched types" (expected `Measurement<Meters>`, found `Measurement<Feet>`)
+    }
+    
+    #[test]
+    fn simulation_retries_until_success() {
+        use application_level::ApplicationBuilder;
+        use rust_style::{ProcessResult, Processor};
+        use std::cell::Cell;
+        
+        /// Asks for a retry the first time, then sums its input
+        struct FlakyOnce {
+            calls: Cell<usize>,
+        }
+        
+        impl Processor for FlakyOnce {
+            type Input = Vec<i32>;
+            type Output = i32;
+            
+            fn process(&self, input: Vec<i32>) -> ProcessResult<i32> {
+                self.calls.set(self.calls.get() + 1);
+                if self.calls.get() == 1 {
+                    ProcessResult::Retry("warming up".to_string())
+                } else {
+                    ProcessResult::Success(input.iter().sum())
+                }
+            }
+        }
+        
+        let app = ApplicationBuilder::new()
+            .workers(2)
+            .worker_iterations(10)
+            .processor(FlakyOnce { calls: Cell::new(0) })
+            .max_attempts(2)
+            .build();
+        assert_eq!(app.run_simulation().unwrap(), "Simulation completed. Result: 15, Counter: 20");
+        
+        // With a single attempt the same Retry surfaces as the error
+        let app = ApplicationBuilder::new()
+            .workers(1)
+            .worker_iterations(1)
+            .processor(FlakyOnce { calls: Cell::new(0) })
+            .max_attempts(1)
+            .build();
+        assert!(matches!(app.run_simulation(), Err(rust_style::ProcessError::Retry(_))));
+    }
+    
+    #[test]
+    fn retry_until_success() {
+        use std::cell::Cell;
+        
+        // Cell gives the Fn closure interior mutability to count its calls
+        let calls = Cell::new(0);
+        let result = rust_style::run_with_retry(5, Duration::from_millis(1), || {
+            calls.set(calls.get() + 1);
+            if calls.get() < 3 {
+                rust_style::ProcessResult::Retry("not yet".to_string())
+            } else {
+                rust_style::ProcessResult::Success(calls.get())
+            }
+        });
+        assert!(matches!(result, rust_style::ProcessResult::Success(3)));
+        
+        // Fatal stops immediately; exhausted attempts return the last Retry
+        calls.set(0);
+        let fatal: rust_style::ProcessResult<()> = rust_style::run_with_retry(5, Duration::ZERO, || {
+            calls.set(calls.get() + 1);
+            rust_style::ProcessResult::Fatal("broken".to_string())
+        });
+        assert!(matches!(fatal, rust_style::ProcessResult::Fatal(_)));
+        assert_eq!(calls.get(), 1);
+        
+        let exhausted: rust_style::ProcessResult<()> = rust_style::run_with_retry(2, Duration::ZERO, || {
+            rust_style::ProcessResult::Retry("still busy".to_string())
+        });
+        assert!(matches!(exhausted, rust_style::ProcessResult::Retry(_)));
     }
     
     #[test]
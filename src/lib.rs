@@ -0,0 +1,1940 @@
+//! The demos behind the `rust-strengths-demo` binary, exposed as a library so they can be
+//! reused from other crates and exercised by the integration tests under `tests/`.
+
+use num_traits::{ToPrimitive, Zero};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, AtomicUsize, Ordering};
+use std::panic;
+use std::sync::{mpsc, Arc, Barrier, Condvar, Mutex, PoisonError, RwLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Used by the exported narrate! and essential! macros
+#[doc(hidden)]
+pub use tracing;
+
+/// How the demos' narration reaches the user
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Narration {
+    /// Nothing at all, e.g. while JSON goes to stdout
+    Off,
+    /// The decorated emoji output, printed straight to stdout
+    Pretty,
+    /// INFO events through `tracing`, so subscribers can filter, redirect, or JSON-format them
+    Log,
+    /// Only the essential results (category sums, final count), printed to stdout
+    Quiet,
+}
+
+static NARRATION: AtomicU8 = AtomicU8::new(Narration::Pretty as u8);
+
+/// Chooses where narration goes for the rest of the process (default: `Pretty`)
+pub fn set_narration(mode: Narration) {
+    NARRATION.store(mode as u8, Ordering::Relaxed);
+}
+
+pub fn narration() -> Narration {
+    match NARRATION.load(Ordering::Relaxed) {
+        0 => Narration::Off,
+        1 => Narration::Pretty,
+        2 => Narration::Log,
+        _ => Narration::Quiet,
+    }
+}
+
+/// Narrates a demo step according to the current `Narration` mode
+#[macro_export]
+macro_rules! narrate {
+    () => {
+        if $crate::narration() == $crate::Narration::Pretty {
+            println!();
+        }
+    };
+    ($($arg:tt)*) => {
+        match $crate::narration() {
+            $crate::Narration::Pretty => println!($($arg)*),
+            $crate::Narration::Log => $crate::tracing::info!($($arg)*),
+            $crate::Narration::Quiet | $crate::Narration::Off => {}
+        }
+    };
+}
+
+/// Like `narrate!`, but for essential results that are still printed in `Quiet` mode
+#[macro_export]
+macro_rules! essential {
+    ($($arg:tt)*) => {
+        match $crate::narration() {
+            $crate::Narration::Pretty | $crate::Narration::Quiet => println!($($arg)*),
+            $crate::Narration::Log => $crate::tracing::info!($($arg)*),
+            $crate::Narration::Off => {}
+        }
+    };
+}
+
+// Teaching material for the README walkthrough; exercised by its own tests
+#[allow(dead_code)]
+mod educational_examples;
+
+/// Categories produced by generate_sample_data
+pub const KNOWN_CATEGORIES: [&str; 4] = ["Alpha", "Beta", "Gamma", "Delta"];
+
+/// Capacity of each worker's inbox in the channel pipeline
+pub const PIPELINE_CAPACITY: usize = 64;
+
+/// Increments each thread performs in the concurrent counter demo
+pub const COUNTER_DEMO_INCREMENTS: u64 = 100_000;
+
+/// Increments each healthy worker performs in the panic-recovery demo
+pub const PANIC_DEMO_INCREMENTS: u64 = 1000;
+
+/// Workload shape for the reader/writer comparison
+pub const READS_PER_READER: usize = 200;
+pub const WRITES_PER_WRITER: usize = 10;
+
+/// Transfers each thread makes in the deadlock demo, and each mutex's starting balance
+pub const DEADLOCK_DEMO_TRANSFERS: usize = 500;
+pub const DEADLOCK_DEMO_BALANCE: i64 = 1000;
+
+/// Everything run_demo needs; the binary builds it from its command line
+#[derive(Debug, Clone)]
+pub struct DemoConfig {
+    pub threads: usize,
+    pub size: usize,
+    pub rules: ValidationRules,
+    pub load_data: Option<String>,
+    pub save_data: Option<String>,
+    /// Print a per-category histogram of values with this many buckets
+    pub histogram_bins: Option<usize>,
+}
+
+impl Default for DemoConfig {
+    fn default() -> Self {
+        DemoConfig {
+            threads: 4,
+            size: 1000,
+            rules: ValidationRules::default(),
+            load_data: None,
+            save_data: None,
+            histogram_bins: None,
+        }
+    }
+}
+
+/// Machine-readable summary of a demo run (`--format json`)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DemoReport {
+    pub data_points: usize,
+    pub threads: usize,
+    pub category_sums: BTreeMap<String, f64>,
+    pub final_count: u64,
+    /// Ctrl-C stopped the run early; later stages are missing
+    #[serde(default)]
+    pub interrupted: bool,
+    /// Wall-clock milliseconds per demo stage
+    pub timings: BTreeMap<String, f64>,
+}
+
+/// Outcome of the panic-recovery demo
+#[derive(Debug)]
+pub struct PanicRecoveryReport {
+    pub panicked_workers: Vec<usize>,
+    pub final_count: u64,
+}
+
+/// Outcome of the crossbeam MPMC work queue demo
+#[cfg(feature = "crossbeam")]
+#[derive(Debug)]
+pub struct CrossbeamReport {
+    pub produced: usize,
+    pub consumed: usize,
+    pub per_consumer: Vec<usize>,
+}
+
+/// Outcome of the Condvar producer/consumer demo
+#[derive(Debug)]
+pub struct CondvarReport {
+    pub produced: usize,
+    pub consumed: usize,
+    pub producer_waits: usize,
+    pub consumer_waits: usize,
+}
+
+/// When one barrier worker finished phase 1 and began phase 2, relative to the demo start
+#[derive(Debug, Clone, Copy)]
+pub struct PhaseTimes {
+    pub phase1_end: Duration,
+    pub phase2_start: Duration,
+}
+
+/// Summary statistics over a numeric slice
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub count: usize,
+    pub mean: f64,
+    /// Population variance
+    pub variance: f64,
+    pub min: f64,
+    pub max: f64,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+}
+
+/// Timing for one counter flavour in the bench-counter comparison
+#[derive(Debug)]
+pub struct CounterBenchmark {
+    pub name: &'static str,
+    pub final_count: u64,
+    pub nanos_per_increment: f64,
+}
+
+/// Represents a data point in our processing pipeline
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataPoint {
+    pub id: u32,
+    pub value: f64,
+    pub category: String,
+    pub timestamp: u64,
+}
+
+/// Custom error type demonstrating Rust's error handling
+#[derive(Debug)]
+pub enum ProcessingError {
+    InvalidData(String),
+    ComputationError(String),
+    IoError(std::io::Error),
+}
+
+impl fmt::Display for ProcessingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProcessingError::InvalidData(msg) => write!(f, "invalid data: {}", msg),
+            ProcessingError::ComputationError(msg) => write!(f, "computation failed: {}", msg),
+            ProcessingError::IoError(e) => write!(f, "I/O error: {}", e),
+        }
+    }
+}
+
+impl Error for ProcessingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ProcessingError::IoError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ProcessingError {
+    fn from(error: std::io::Error) -> Self {
+        ProcessingError::IoError(error)
+    }
+}
+
+/// Why a safe division was refused
+#[derive(Debug, PartialEq, Eq)]
+pub enum DivError {
+    DivByZero,
+    /// An operand has no f64 representation (e.g. an out-of-range big integer)
+    NotRepresentable,
+}
+
+impl fmt::Display for DivError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DivError::DivByZero => write!(f, "Division by zero"),
+            DivError::NotRepresentable => write!(f, "Operand not representable as f64"),
+        }
+    }
+}
+
+impl Error for DivError {}
+
+/// Limits a data point must satisfy before it is processed
+#[derive(Debug, Clone)]
+pub struct ValidationRules {
+    pub min_value: f64,
+    pub max_value: f64,
+    pub allowed_categories: Vec<String>,
+}
+
+impl Default for ValidationRules {
+    /// Accepts everything generate_sample_data produces
+    fn default() -> Self {
+        ValidationRules {
+            min_value: -100.0,
+            max_value: 100.0,
+            allowed_categories: KNOWN_CATEGORIES.map(String::from).to_vec(),
+        }
+    }
+}
+
+/// Result type alias for cleaner code
+pub type ProcessingResult<T> = Result<T, ProcessingError>;
+
+/// Demonstrates memory safety and zero-cost abstractions
+pub fn generate_sample_data(size: usize) -> Vec<DataPoint> {
+    narrate!("🔧 Generating {} data points...", size);
+    
+    // Iterator chains compile to highly optimized loops
+    (0..size).map(make_data_point).collect()
+}
+
+/// Builds the i-th sample data point
+// 3.14159 rather than PI on purpose: exact multiples of PI would put every sin() at zero
+#[allow(clippy::approx_constant)]
+fn make_data_point(i: usize) -> DataPoint {
+    DataPoint {
+        id: i as u32,
+        value: (i as f64 * 3.14159).sin() * 100.0,
+        category: match i % 4 {
+            0 => "Alpha".to_string(),
+            1 => "Beta".to_string(),
+            2 => "Gamma".to_string(),
+            _ => "Delta".to_string(),
+        },
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() + i as u64,
+    }
+}
+
+/// Dataset file formats, picked from the file extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFormat {
+    Json,
+    Csv,
+}
+
+impl DataFormat {
+    pub fn from_path(path: &str) -> ProcessingResult<Self> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+        
+        match extension.as_deref() {
+            Some("json") => Ok(DataFormat::Json),
+            Some("csv") => Ok(DataFormat::Csv),
+            _ => Err(ProcessingError::InvalidData(format!(
+                "{}: expected a .json or .csv extension",
+                path
+            ))),
+        }
+    }
+}
+
+/// Writes the dataset so a later run can replay it with --load-data
+pub fn save_data(data: &[DataPoint], path: &str) -> ProcessingResult<()> {
+    match DataFormat::from_path(path)? {
+        DataFormat::Json => {
+            let json = serde_json::to_string_pretty(data)
+                .map_err(|e| ProcessingError::InvalidData(format!("{}: {}", path, e)))?;
+            fs::write(path, json)?;
+        }
+        DataFormat::Csv => {
+            let csv_error = |e: csv::Error| ProcessingError::InvalidData(format!("{}: {}", path, e));
+            let mut writer = csv::Writer::from_path(path).map_err(csv_error)?;
+            for point in data {
+                writer.serialize(point).map_err(csv_error)?;
+            }
+            writer.flush()?;
+        }
+    }
+    
+    narrate!("💾 Saved {} data points to {}", data.len(), path);
+    Ok(())
+}
+
+/// One row of the category sums CSV
+#[derive(Serialize)]
+struct CategorySum<'a> {
+    category: &'a str,
+    sum: f64,
+}
+
+/// Writes the category sums as a `category,sum` CSV, in category order
+pub fn save_category_sums(sums: &BTreeMap<String, f64>, path: &str) -> ProcessingResult<()> {
+    let csv_error = |e: csv::Error| ProcessingError::InvalidData(format!("{}: {}", path, e));
+    let mut writer = csv::Writer::from_path(path).map_err(csv_error)?;
+    for (category, &sum) in sums {
+        writer.serialize(CategorySum { category, sum }).map_err(csv_error)?;
+    }
+    writer.flush()?;
+    
+    narrate!("💾 Saved {} category sums to {}", sums.len(), path);
+    Ok(())
+}
+
+/// Reads a dataset written by save_data, rejecting it if any point fails validation
+pub fn load_data(path: &str, rules: &ValidationRules) -> ProcessingResult<Vec<DataPoint>> {
+    let data: Vec<DataPoint> = match DataFormat::from_path(path)? {
+        DataFormat::Json => serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| ProcessingError::InvalidData(format!("{}: {}", path, e)))?,
+        DataFormat::Csv => csv::Reader::from_path(path)
+            .and_then(|mut reader| reader.deserialize().collect())
+            .map_err(|e| ProcessingError::InvalidData(format!("{}: {}", path, e)))?,
+    };
+    
+    for point in &data {
+        validate_data_point(point, rules)?;
+    }
+    
+    narrate!("📂 Loaded {} data points from {}", data.len(), path);
+    Ok(data)
+}
+
+/// Demonstrates pattern matching and error handling
+pub fn validate_data_point(point: &DataPoint, rules: &ValidationRules) -> ProcessingResult<()> {
+    match point {
+        DataPoint { value, .. } if value.is_nan() || value.is_infinite() => {
+            Err(ProcessingError::InvalidData(format!("Invalid value for ID {}", point.id)))
+        }
+        DataPoint { value, .. } if *value < rules.min_value || *value > rules.max_value => {
+            Err(ProcessingError::InvalidData(format!(
+                "value {} outside [{}, {}] for ID {}",
+                value, rules.min_value, rules.max_value, point.id
+            )))
+        }
+        DataPoint { category, .. } if category.is_empty() => {
+            Err(ProcessingError::InvalidData(format!("Empty category for ID {}", point.id)))
+        }
+        DataPoint { category, .. } if !rules.allowed_categories.contains(category) => {
+            Err(ProcessingError::InvalidData(format!(
+                "category '{}' not allowed for ID {}",
+                category, point.id
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Demonstrates parallel processing with Rayon (data parallelism)
+pub fn parallel_data_processing(
+    data: &[DataPoint],
+    rules: &ValidationRules,
+) -> ProcessingResult<HashMap<String, f64>> {
+    let _span = tracing::info_span!("parallel_processing", points = data.len()).entered();
+    narrate!("⚡ Processing data in parallel using Rayon...");
+    
+    let start = Instant::now();
+    
+    // Parallel iterator - automatically distributes work across CPU cores
+    let validated_data: ProcessingResult<Vec<_>> = data
+        .par_iter()  // Convert to parallel iterator
+        .map(|point| {
+            // Validate each point
+            validate_data_point(point, rules)?;
+            Ok((point.category.clone(), point.value))
+        })
+        .collect();
+    
+    let result = validated_data?
+        .into_iter()
+        .fold(HashMap::new(), |mut acc: HashMap<String, f64>, (category, value)| {
+            *acc.entry(category).or_insert(0.0) += value;
+            acc
+        });
+    
+    let duration = start.elapsed();
+    narrate!("✅ Parallel processing completed in {:?}", duration);
+    
+    Ok(result)
+}
+
+/// Runs parallel_data_processing on a dedicated rayon pool instead of the global one
+pub fn parallel_data_processing_on_pool(
+    data: &[DataPoint],
+    rules: &ValidationRules,
+    num_threads: usize,
+) -> ProcessingResult<HashMap<String, f64>> {
+    // num_threads(0) lets rayon pick one thread per core
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()
+        .map_err(|e| ProcessingError::ComputationError(e.to_string()))?;
+    
+    narrate!("🧵 Rayon pool size: {} threads", pool.current_num_threads());
+    
+    // Every par_iter inside install() runs on this pool's threads
+    pool.install(|| parallel_data_processing(data, rules))
+}
+
+/// Value range and bucket counts of one category's histogram
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub min: f64,
+    pub max: f64,
+    pub counts: Vec<usize>,
+}
+
+/// Buckets each category's values into `bins` equal-width ranges spanning the whole dataset,
+/// so every category's histogram shares the same axis
+pub fn category_histograms(data: &[DataPoint], bins: usize) -> BTreeMap<String, Histogram> {
+    let bins = bins.max(1);
+    let min = data.iter().map(|point| point.value).fold(f64::INFINITY, f64::min);
+    let max = data.iter().map(|point| point.value).fold(f64::NEG_INFINITY, f64::max);
+    let width = (max - min) / bins as f64;
+    
+    // The maximum itself would land one past the end, so clamp it into the last bucket
+    let bucket = |value: f64| {
+        if width > 0.0 { (((value - min) / width) as usize).min(bins - 1) } else { 0 }
+    };
+    
+    // Reduce-by-group: each rayon job counts into its own map, then the maps are merged
+    data.par_iter()
+        .fold(BTreeMap::new, |mut acc: BTreeMap<String, Vec<usize>>, point| {
+            acc.entry(point.category.clone()).or_insert_with(|| vec![0; bins])[bucket(point.value)] += 1;
+            acc
+        })
+        .reduce(BTreeMap::new, |mut left, right| {
+            for (category, counts) in right {
+                let merged = left.entry(category).or_insert_with(|| vec![0; bins]);
+                for (total, count) in merged.iter_mut().zip(counts) {
+                    *total += count;
+                }
+            }
+            left
+        })
+        .into_iter()
+        .map(|(category, counts)| (category, Histogram { min, max, counts }))
+        .collect()
+}
+
+/// Prints each category's histogram as rows of ASCII bars
+pub fn print_histograms(histograms: &BTreeMap<String, Histogram>, bins: usize) {
+    const BAR_WIDTH: usize = 40;
+    
+    essential!("📊 Value histograms ({} buckets):", bins.max(1));
+    for (category, histogram) in histograms {
+        essential!("  {}:", category);
+        let width = (histogram.max - histogram.min) / histogram.counts.len() as f64;
+        let tallest = histogram.counts.iter().copied().max().unwrap_or(0).max(1);
+        for (i, &count) in histogram.counts.iter().enumerate() {
+            let low = histogram.min + width * i as f64;
+            essential!("    [{:>8.2}, {:>8.2}) {:<width$} {}",
+                       low, low + width, "#".repeat(count * BAR_WIDTH / tallest), count, width = BAR_WIDTH);
+        }
+    }
+}
+
+/// Generic statistics: one monomorphized copy per element type, each a tight loop over that type
+pub fn stats<T: Into<f64> + Copy>(data: &[T]) -> Option<Stats> {
+    if data.is_empty() {
+        return None;
+    }
+    
+    let mut values: Vec<f64> = data.iter().map(|&x| x.into()).collect();
+    values.sort_by(f64::total_cmp);
+    
+    let count = values.len();
+    let mean = values.iter().sum::<f64>() / count as f64;
+    let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count as f64;
+    
+    Some(Stats {
+        count,
+        mean,
+        variance,
+        min: values[0],
+        max: values[count - 1],
+        p50: percentile(&values, 0.50),
+        p90: percentile(&values, 0.90),
+        p99: percentile(&values, 0.99),
+    })
+}
+
+/// Linearly interpolated percentile of an already sorted, non-empty slice
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+/// Demonstrates safe concurrency with shared state; workers stop early once `shutdown` is set
+pub fn concurrent_counter_demo(num_threads: usize, increments: u64, shutdown: &Arc<AtomicBool>) -> u64 {
+    let _span = tracing::info_span!("concurrent_counter", threads = num_threads, increments).entered();
+    narrate!("🔄 Demonstrating safe concurrent access with {} threads...", num_threads);
+    
+    // Arc (Atomically Reference Counted) allows sharing between threads
+    // Mutex ensures safe access to shared data
+    let counter = Arc::new(Mutex::new(0u64));
+    let mut handles = Vec::new();
+    
+    let start = Instant::now();
+    
+    for i in 0..num_threads {
+        let counter_clone = Arc::clone(&counter);
+        let shutdown = Arc::clone(shutdown);
+        
+        let handle = thread::spawn(move || {
+            let mut done = 0u64;
+            
+            // Each thread does some work, checking the shutdown flag between increments
+            while done < increments && !shutdown.load(Ordering::Relaxed) {
+                // Lock is automatically released when it goes out of scope
+                let mut num = counter_clone.lock().unwrap();
+                *num += 1;
+                done += 1;
+                // Lock is dropped here - RAII (Resource Acquisition Is Initialization)
+            }
+            
+            narrate!("Thread {} completed {} increments", i, done);
+            done
+        });
+        
+        handles.push(handle);
+    }
+    
+    // Wait for all threads to complete - also on shutdown, so no work is torn off mid-update
+    let completed: u64 = handles.into_iter().map(|handle| handle.join().unwrap()).sum();
+    
+    let final_count = *counter.lock().unwrap();
+    let duration = start.elapsed();
+    debug_assert_eq!(final_count, completed);
+    
+    if shutdown.load(Ordering::Relaxed) {
+        narrate!("🛑 Shutdown requested: stopped after {:?}", duration);
+    } else {
+        narrate!("✅ Concurrent counting completed in {:?}", duration);
+    }
+    essential!("📊 Final count: {} (expected: {})", final_count, num_threads as u64 * increments);
+    
+    final_count
+}
+
+/// Makes "fearless concurrency" measurable: the same increment workload under three primitives
+pub fn bench_counters(num_threads: usize, increments: usize) -> Vec<CounterBenchmark> {
+    narrate!("⏱️  Benchmarking counters: {} threads × {} increments...", num_threads, increments);
+    
+    let total_ops = (num_threads * increments).max(1) as f64;
+    let mut results = Vec::new();
+    
+    let mutex = Mutex::new(0u64);
+    let elapsed = time_counter_workload(num_threads, increments, || *mutex.lock().unwrap() += 1);
+    results.push(CounterBenchmark {
+        name: "Mutex<u64>",
+        final_count: *mutex.lock().unwrap(),
+        nanos_per_increment: elapsed.as_nanos() as f64 / total_ops,
+    });
+    
+    // Lock-free: a single atomic read-modify-write instruction per increment
+    let atomic = AtomicU64::new(0);
+    let elapsed = time_counter_workload(num_threads, increments, || {
+        atomic.fetch_add(1, Ordering::Relaxed);
+    });
+    results.push(CounterBenchmark {
+        name: "AtomicU64",
+        final_count: atomic.load(Ordering::Relaxed),
+        nanos_per_increment: elapsed.as_nanos() as f64 / total_ops,
+    });
+    
+    // Every increment needs the exclusive write lock, so RwLock gains nothing here
+    let rwlock = RwLock::new(0u64);
+    let elapsed = time_counter_workload(num_threads, increments, || *rwlock.write().unwrap() += 1);
+    results.push(CounterBenchmark {
+        name: "RwLock<u64>",
+        final_count: *rwlock.read().unwrap(),
+        nanos_per_increment: elapsed.as_nanos() as f64 / total_ops,
+    });
+    
+    for result in &results {
+        narrate!("📊 {:<12} | {:>8.2} ns/increment | final count: {}",
+                 result.name, result.nanos_per_increment, result.final_count);
+    }
+    
+    results
+}
+
+/// Runs `increment` `increments` times on each of `num_threads` scoped threads
+fn time_counter_workload<F>(num_threads: usize, increments: usize, increment: F) -> Duration
+where
+    F: Fn() + Sync,
+{
+    let start = Instant::now();
+    
+    thread::scope(|scope| {
+        for _ in 0..num_threads {
+            scope.spawn(|| {
+                for _ in 0..increments {
+                    increment();
+                }
+            });
+        }
+    });
+    
+    start.elapsed()
+}
+
+/// Demonstrates ownership and borrowing
+pub fn ownership_demo() {
+    narrate!("🏠 Demonstrating Rust's ownership system...");
+    
+    // String creation - heap allocated
+    let mut original = String::from("Hello, Rust!");
+    narrate!("Original: {}", original);
+    
+    // Borrowing (immutable reference) - no ownership transfer
+    let borrowed = &original;
+    narrate!("Borrowed: {}", borrowed);
+    
+    // Mutable borrowing
+    let mutable_borrow = &mut original;
+    mutable_borrow.push_str(" 🦀");
+    narrate!("After mutation: {}", mutable_borrow);
+    
+    // Move semantics - ownership transferred
+    let moved = original;  // original is no longer accessible
+    narrate!("Moved: {}", moved);
+    
+    // This would cause a compile error:
+    // narrate!("Original after move: {}", original);
+    
+    narrate!("✅ No memory leaks, no dangling pointers, all memory safely managed!");
+}
+
+/// Demonstrates OS thread spawning (see async_demo for the async/await version)
+pub fn threading_demo() -> ProcessingResult<()> {
+    narrate!("🚀 Demonstrating thread spawning...");
+    
+    let handles: Vec<_> = (0..3)
+        .map(|i| {
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(100 + i * 50));
+                format!("Task {} completed", i + 1)
+            })
+        })
+        .collect();
+    
+    // Wait for all tasks
+    for handle in handles {
+        let result = handle.join().unwrap();
+        narrate!("📡 {}", result);
+    }
+    
+    narrate!("✅ All threaded tasks completed!");
+    Ok(())
+}
+
+/// Demonstrates async/await: lightweight tasks multiplexed onto a small thread pool
+#[cfg(feature = "async")]
+pub async fn async_demo() -> ProcessingResult<Vec<String>> {
+    narrate!("⏳ Demonstrating async/await with tokio...");
+    
+    let start = Instant::now();
+    
+    fn spawn_task(n: u64, delay_ms: u64) -> tokio::task::JoinHandle<String> {
+        tokio::spawn(async move {
+            // Simulated async I/O - the task yields instead of blocking its thread
+            tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            format!("Async task {} completed", n)
+        })
+    }
+    
+    // All three tasks sleep at the same time, so this takes ~200ms rather than ~450ms
+    let (first, second, third) = tokio::join!(spawn_task(1, 100), spawn_task(2, 150), spawn_task(3, 200));
+    
+    let results = [first, second, third]
+        .into_iter()
+        .map(|joined| joined.map_err(|e| ProcessingError::ComputationError(e.to_string())))
+        .collect::<ProcessingResult<Vec<_>>>()?;
+    
+    for result in &results {
+        narrate!("📡 {}", result);
+    }
+    narrate!("✅ All async tasks completed in {:?}", start.elapsed());
+    
+    Ok(results)
+}
+
+/// Demonstrates message passing: producer -> workers -> collector over mpsc channels
+pub fn channel_pipeline_demo(
+    data: &[DataPoint],
+    num_workers: usize,
+    rules: &ValidationRules,
+) -> ProcessingResult<Vec<DataPoint>> {
+    let num_workers = num_workers.max(1);
+    narrate!("📬 Demonstrating an mpsc channel pipeline with {} workers...", num_workers);
+    
+    let start = Instant::now();
+    
+    // Every worker reports to the collector through a clone of this sender
+    let (result_tx, result_rx) = mpsc::channel::<ProcessingResult<DataPoint>>();
+    let rules = Arc::new(rules.clone());
+    let mut worker_txs = Vec::new();
+    let mut handles = Vec::new();
+    
+    for _ in 0..num_workers {
+        // Bounded inbox: the producer blocks when a worker falls behind (backpressure)
+        let (tx, rx) = mpsc::sync_channel::<DataPoint>(PIPELINE_CAPACITY);
+        let result_tx = result_tx.clone();
+        let rules = Arc::clone(&rules);
+        
+        let handle = thread::spawn(move || {
+            // The loop ends once the producer drops its sender
+            for mut point in rx {
+                let outcome = validate_data_point(&point, &rules).map(|_| {
+                    point.value = point.value.abs();
+                    point
+                });
+                
+                // The collector hung up early (it saw an error) - stop working
+                if result_tx.send(outcome).is_err() {
+                    break;
+                }
+            }
+        });
+        
+        worker_txs.push(tx);
+        handles.push(handle);
+    }
+    
+    // Drop our own sender so the collector finishes when the last worker does
+    drop(result_tx);
+    
+    // The producer thread owns its copy, so each point can move on into the pipeline
+    let points = data.to_vec();
+    let producer = thread::spawn(move || {
+        for (i, point) in points.into_iter().enumerate() {
+            let tx = &worker_txs[i % worker_txs.len()];
+            if tx.send(point).is_err() {
+                break;
+            }
+        }
+        // worker_txs is dropped here, closing every worker's inbox
+    });
+    
+    // The collector: ownership of each DataPoint moves through the pipeline, no locks needed
+    let collected: ProcessingResult<Vec<_>> = result_rx.into_iter().collect();
+    
+    producer.join().unwrap();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    
+    let collected = collected?;
+    let duration = start.elapsed();
+    
+    narrate!("✅ Pipeline moved {} items in {:?}", collected.len(), duration);
+    narrate!("📊 Throughput: {:.0} items/sec", collected.len() as f64 / duration.as_secs_f64());
+    
+    Ok(collected)
+}
+
+/// Demonstrates a crossbeam MPMC work queue: every consumer pulls from one shared bounded
+/// channel, and select! lets it watch a shutdown channel at the same time
+#[cfg(feature = "crossbeam")]
+pub fn crossbeam_pipeline_demo(producers: usize, consumers: usize, items_per_producer: usize) -> CrossbeamReport {
+    use crossbeam_channel::{bounded, select};
+    
+    let consumers = consumers.max(1);
+    narrate!("🔀 Demonstrating a crossbeam MPMC queue ({} producers, {} consumers)...", producers, consumers);
+    
+    let start = Instant::now();
+    let (work_tx, work_rx) = bounded::<DataPoint>(PIPELINE_CAPACITY);
+    let (shutdown_tx, shutdown_rx) = bounded::<()>(0);
+    
+    let per_consumer = thread::scope(|scope| {
+        // Unlike mpsc::Receiver, a crossbeam Receiver is Clone: consumers share one queue
+        // and whoever is free takes the next item, so no producer has to pick a worker
+        let consumer_handles: Vec<_> = (0..consumers)
+            .map(|_| {
+                let work_rx = work_rx.clone();
+                let shutdown_rx = shutdown_rx.clone();
+                scope.spawn(move || {
+                    let mut consumed = 0;
+                    loop {
+                        select! {
+                            recv(work_rx) -> point => match point {
+                                Ok(_) => consumed += 1,
+                                Err(_) => break,  // Every sender is gone
+                            },
+                            recv(shutdown_rx) -> _ => {
+                                // Finish whatever is already queued, then stop
+                                consumed += work_rx.try_iter().count();
+                                break;
+                            }
+                        }
+                    }
+                    consumed
+                })
+            })
+            .collect();
+        
+        let producer_handles: Vec<_> = (0..producers)
+            .map(|p| {
+                let work_tx = work_tx.clone();
+                scope.spawn(move || {
+                    for i in 0..items_per_producer {
+                        work_tx.send(make_data_point(p * items_per_producer + i)).unwrap();
+                    }
+                })
+            })
+            .collect();
+        
+        for handle in producer_handles {
+            handle.join().unwrap();
+        }
+        
+        // Producers are done; dropping the last sender of shutdown disconnects it,
+        // which wakes every consumer's select! at once
+        drop(shutdown_tx);
+        consumer_handles.into_iter().map(|handle| handle.join().unwrap()).collect::<Vec<_>>()
+    });
+    
+    let report = CrossbeamReport {
+        produced: producers * items_per_producer,
+        consumed: per_consumer.iter().sum(),
+        per_consumer,
+    };
+    
+    narrate!("✅ Consumed {} of {} items in {:?}", report.consumed, report.produced, start.elapsed());
+    narrate!("📊 Items per consumer: {:?}", report.per_consumer);
+    narrate!("📝 Note: std mpsc allows a single consumer, so channel_pipeline_demo gives each worker its");
+    narrate!("   own inbox and deals work round-robin; an MPMC queue balances itself across consumers.");
+    
+    report
+}
+
+/// Demonstrates RwLock: many readers share the data at once, writers get exclusive access
+pub fn rwlock_demo(data: &[DataPoint], readers: usize, writers: usize) -> usize {
+    narrate!("📚 Demonstrating RwLock with {} readers and {} writers...", readers, writers);
+    
+    let base_id = data.len();
+    
+    // Readers take shared read guards - any number can hold one at the same time
+    let shared = Arc::new(RwLock::new(data.to_vec()));
+    let (read_handle, write_handle) = (Arc::clone(&shared), Arc::clone(&shared));
+    let rwlock_time = run_reader_writer_workload(
+        readers,
+        writers,
+        move || {
+            let points = read_handle.read().unwrap();
+            points.iter().map(|point| point.value).sum::<f64>()
+        },
+        move |n| write_handle.write().unwrap().push(make_data_point(base_id + n)),
+    );
+    
+    // The same workload with a Mutex serializes the readers too
+    let exclusive = Arc::new(Mutex::new(data.to_vec()));
+    let (read_handle, write_handle) = (Arc::clone(&exclusive), Arc::clone(&exclusive));
+    let mutex_time = run_reader_writer_workload(
+        readers,
+        writers,
+        move || {
+            let points = read_handle.lock().unwrap();
+            points.iter().map(|point| point.value).sum::<f64>()
+        },
+        move |n| write_handle.lock().unwrap().push(make_data_point(base_id + n)),
+    );
+    
+    let final_len = shared.read().unwrap().len();
+    let total_reads = (readers * READS_PER_READER) as f64;
+    
+    narrate!("✅ RwLock: {:?} ({:.0} reads/sec)", rwlock_time, total_reads / rwlock_time.as_secs_f64());
+    narrate!("✅ Mutex:  {:?} ({:.0} reads/sec)", mutex_time, total_reads / mutex_time.as_secs_f64());
+    narrate!("📊 RwLock speedup for this read-heavy workload: {:.2}x",
+             mutex_time.as_secs_f64() / rwlock_time.as_secs_f64());
+    narrate!("📊 Final data length: {} (expected: {})", final_len, base_id + writers * WRITES_PER_WRITER);
+    narrate!("⚠️  Note: a steady stream of readers can starve writers (or vice versa, depending on");
+    narrate!("   the platform's lock policy) - RwLock pays off when reads vastly outnumber writes.");
+    
+    final_len
+}
+
+/// Runs `readers` threads calling `read` and `writers` threads calling `write`, returning the wall time
+fn run_reader_writer_workload<R, W>(readers: usize, writers: usize, read: R, write: W) -> Duration
+where
+    R: Fn() -> f64 + Send + Sync + 'static,
+    W: Fn(usize) + Send + Sync + 'static,
+{
+    let read = Arc::new(read);
+    let write = Arc::new(write);
+    let mut handles = Vec::new();
+    
+    let start = Instant::now();
+    
+    for _ in 0..readers {
+        let read = Arc::clone(&read);
+        handles.push(thread::spawn(move || {
+            for _ in 0..READS_PER_READER {
+                std::hint::black_box(read());
+            }
+        }));
+    }
+    
+    for w in 0..writers {
+        let write = Arc::clone(&write);
+        handles.push(thread::spawn(move || {
+            for j in 0..WRITES_PER_WRITER {
+                write(w * WRITES_PER_WRITER + j);
+            }
+        }));
+    }
+    
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    
+    start.elapsed()
+}
+
+/// Demonstrates scoped threads: workers borrow `data` directly, no Arc or 'static needed
+pub fn scoped_threads_demo(data: &[DataPoint], num_threads: usize) -> Vec<f64> {
+    let chunk_size = data.len().div_ceil(num_threads.max(1)).max(1);
+    narrate!("🔭 Demonstrating scoped threads over chunks of {} points...", chunk_size);
+    
+    let start = Instant::now();
+    
+    // Every thread spawned inside the scope is joined before it returns,
+    // so the compiler knows the borrowed slice outlives them all
+    let chunk_sums: Vec<f64> = thread::scope(|scope| {
+        let handles: Vec<_> = data
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(|point| point.value).sum::<f64>()))
+            .collect();
+        
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+    
+    let duration = start.elapsed();
+    
+    for (i, sum) in chunk_sums.iter().enumerate() {
+        narrate!("  Chunk {}: {:.2}", i, sum);
+    }
+    narrate!("✅ Scoped processing completed in {:?}", duration);
+    narrate!("📝 Note: concurrent_counter_demo needs Arc because thread::spawn requires 'static data;");
+    narrate!("   thread::scope lets threads borrow from the stack because they can't outlive it.");
+    
+    chunk_sums
+}
+
+/// Per-item work for the load-balancing demo: every point takes BASE_WORK_ROUNDS of busywork,
+/// and SKEWED_CATEGORY points take SKEW_FACTOR times as long
+const BASE_WORK_ROUNDS: u32 = 20;
+const SKEW_FACTOR: u32 = 50;
+const SKEWED_CATEGORY: &str = "Delta";
+
+/// Burns CPU in proportion to the point's category, then returns its value unchanged
+fn skewed_value(point: &DataPoint) -> f64 {
+    let rounds = if point.category == SKEWED_CATEGORY { BASE_WORK_ROUNDS * SKEW_FACTOR } else { BASE_WORK_ROUNDS };
+    let mut x = point.value;
+    for _ in 0..rounds {
+        x = std::hint::black_box(x.sin());
+    }
+    std::hint::black_box(x);
+    point.value
+}
+
+/// Category sums via rayon: idle threads steal queued work from busy ones
+pub fn work_stealing_sums(data: &[DataPoint]) -> BTreeMap<String, f64> {
+    data.par_iter()
+        .fold(BTreeMap::new, |mut acc: BTreeMap<String, f64>, point| {
+            *acc.entry(point.category.clone()).or_insert(0.0) += skewed_value(point);
+            acc
+        })
+        .reduce(BTreeMap::new, |mut left, right| {
+            for (category, sum) in right {
+                *left.entry(category).or_insert(0.0) += sum;
+            }
+            left
+        })
+}
+
+/// Category sums via one equal-sized range per thread, fixed up front
+pub fn static_chunking_sums(data: &[DataPoint], num_threads: usize) -> BTreeMap<String, f64> {
+    let chunk_size = data.len().div_ceil(num_threads.max(1)).max(1);
+    
+    thread::scope(|scope| {
+        let handles: Vec<_> = data
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    let mut sums = BTreeMap::new();
+                    for point in chunk {
+                        *sums.entry(point.category.clone()).or_insert(0.0) += skewed_value(point);
+                    }
+                    sums
+                })
+            })
+            .collect();
+        
+        handles.into_iter().fold(BTreeMap::new(), |mut acc, handle| {
+            for (category, sum) in handle.join().unwrap() {
+                *acc.entry(category).or_insert(0.0) += sum;
+            }
+            acc
+        })
+    })
+}
+
+/// Wall-clock time of each strategy in the load-balancing demo
+#[derive(Debug, Clone, Copy)]
+pub struct LoadBalancingReport {
+    pub work_stealing: Duration,
+    pub static_chunking: Duration,
+}
+
+/// Demonstrates why work stealing wins when per-item cost is uneven
+pub fn load_balancing_demo(data: &[DataPoint], num_threads: usize) -> ProcessingResult<LoadBalancingReport> {
+    narrate!("⚖️  Demonstrating load balancing: work stealing vs static chunking ({} threads)...", num_threads);
+    
+    // Grouping by category puts every slow point in the same range, as data sorted by key often is
+    let mut grouped = data.to_vec();
+    grouped.sort_by(|a, b| a.category.cmp(&b.category));
+    narrate!("   {} points cost {}x the others and now sit together in one contiguous range",
+             SKEWED_CATEGORY, SKEW_FACTOR);
+    
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(num_threads)
+        .build()
+        .map_err(|e| ProcessingError::ComputationError(e.to_string()))?;
+    
+    let start = Instant::now();
+    pool.install(|| work_stealing_sums(&grouped));
+    let work_stealing = start.elapsed();
+    
+    let start = Instant::now();
+    static_chunking_sums(&grouped, num_threads);
+    let static_chunking = start.elapsed();
+    
+    narrate!("✅ Work stealing (rayon):    {:?}", work_stealing);
+    narrate!("✅ Static chunking (scoped): {:?}", static_chunking);
+    narrate!("📊 Work stealing speedup: {:.2}x",
+             static_chunking.as_secs_f64() / work_stealing.as_secs_f64().max(f64::EPSILON));
+    narrate!("📝 Note: a static split is only as fast as its slowest range; rayon keeps splitting");
+    narrate!("   the work and lets idle threads steal from busy ones, so nobody waits on one thread.");
+    
+    Ok(LoadBalancingReport { work_stealing, static_chunking })
+}
+
+/// Demonstrates that a panicking thread is contained: join() reports it and poisoned data is recoverable
+pub fn panic_recovery_demo(num_workers: usize, panicking_worker: usize) -> PanicRecoveryReport {
+    narrate!("💥 Demonstrating panic recovery ({} workers, worker {} panics on purpose)...",
+             num_workers, panicking_worker);
+    
+    let counter = Arc::new(Mutex::new(0u64));
+    
+    // These panics are the point of the demo; keep the default hook from reporting them
+    // on stderr (with a backtrace under RUST_BACKTRACE) as if the program had crashed
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    
+    let handles: Vec<_> = (0..num_workers)
+        .map(|i| {
+            let counter = Arc::clone(&counter);
+            thread::spawn(move || {
+                if i == panicking_worker {
+                    // Panicking while the guard is held poisons the mutex
+                    let _guard = counter.lock().unwrap();
+                    panic!("worker {} failed on purpose", i);
+                }
+                
+                for _ in 0..PANIC_DEMO_INCREMENTS {
+                    // The data behind a poisoned lock is still there - take the guard anyway
+                    let mut count = counter.lock().unwrap_or_else(PoisonError::into_inner);
+                    *count += 1;
+                }
+            })
+        })
+        .collect();
+    
+    // A panic unwinds only its own thread; join() turns it into an ordinary Err
+    let mut panicked_workers = Vec::new();
+    for (i, handle) in handles.into_iter().enumerate() {
+        match handle.join() {
+            Ok(()) => narrate!("  Worker {} finished", i),
+            Err(_) => {
+                narrate!("  Worker {} panicked - join() returned Err", i);
+                panicked_workers.push(i);
+            }
+        }
+    }
+    
+    let poisoned = counter.is_poisoned();
+    let final_count = *counter.lock().unwrap_or_else(PoisonError::into_inner);
+    
+    // catch_unwind does the same for a panic on the current thread
+    let caught = panic::catch_unwind(|| -> u64 { panic!("caught with catch_unwind") });
+    panic::set_hook(default_hook);
+    
+    narrate!("✅ Mutex poisoned: {} - recovered via PoisonError::into_inner", poisoned);
+    narrate!("📊 Count from healthy workers: {}", final_count);
+    narrate!("✅ catch_unwind contained an in-thread panic: {}", caught.is_err());
+    
+    PanicRecoveryReport {
+        panicked_workers,
+        final_count,
+    }
+}
+
+/// Queue contents plus the bookkeeping consumers need to know when to stop
+struct QueueState {
+    items: VecDeque<DataPoint>,
+    producers_remaining: usize,
+}
+
+/// Demonstrates condition variables: a bounded queue where both sides sleep instead of spinning
+pub fn condvar_demo(
+    producers: usize,
+    consumers: usize,
+    items_per_producer: usize,
+    capacity: usize,
+) -> CondvarReport {
+    let consumers = consumers.max(1);
+    let capacity = capacity.max(1);
+    narrate!("🚦 Demonstrating Condvar backpressure ({} producers, {} consumers, capacity {})...",
+             producers, consumers, capacity);
+    
+    let state = Mutex::new(QueueState {
+        items: VecDeque::with_capacity(capacity),
+        producers_remaining: producers,
+    });
+    let not_empty = Condvar::new();
+    let not_full = Condvar::new();
+    let producer_waits = AtomicUsize::new(0);
+    let consumer_waits = AtomicUsize::new(0);
+    
+    let start = Instant::now();
+    
+    let consumed = thread::scope(|scope| {
+        for p in 0..producers {
+            let (state, not_empty, not_full) = (&state, &not_empty, &not_full);
+            let producer_waits = &producer_waits;
+            scope.spawn(move || {
+                for i in 0..items_per_producer {
+                    let point = make_data_point(p * items_per_producer + i);
+                    let mut guard = state.lock().unwrap();
+                    
+                    // Full queue: release the lock and sleep until a consumer makes room
+                    while guard.items.len() >= capacity {
+                        producer_waits.fetch_add(1, Ordering::Relaxed);
+                        guard = not_full.wait(guard).unwrap();
+                    }
+                    
+                    guard.items.push_back(point);
+                    drop(guard);
+                    not_empty.notify_one();
+                }
+                
+                // Wake every consumer so they can notice the last producer is gone
+                state.lock().unwrap().producers_remaining -= 1;
+                not_empty.notify_all();
+            });
+        }
+        
+        let handles: Vec<_> = (0..consumers)
+            .map(|_| {
+                let (state, not_empty, not_full) = (&state, &not_empty, &not_full);
+                let consumer_waits = &consumer_waits;
+                scope.spawn(move || {
+                    let mut count = 0;
+                    loop {
+                        let mut guard = state.lock().unwrap();
+                        
+                        // Empty queue: sleep until a producer pushes or everyone is done
+                        while guard.items.is_empty() && guard.producers_remaining > 0 {
+                            consumer_waits.fetch_add(1, Ordering::Relaxed);
+                            guard = not_empty.wait(guard).unwrap();
+                        }
+                        
+                        match guard.items.pop_front() {
+                            Some(_point) => {
+                                drop(guard);
+                                not_full.notify_one();
+                                count += 1;
+                            }
+                            None => break count,
+                        }
+                    }
+                })
+            })
+            .collect();
+        
+        handles.into_iter().map(|handle| handle.join().unwrap()).sum::<usize>()
+    });
+    
+    let report = CondvarReport {
+        produced: producers * items_per_producer,
+        consumed,
+        producer_waits: producer_waits.into_inner(),
+        consumer_waits: consumer_waits.into_inner(),
+    };
+    
+    narrate!("✅ Consumed {} of {} items in {:?}", report.consumed, report.produced, start.elapsed());
+    narrate!("📊 Producers waited {} times (queue full), consumers waited {} times (queue empty)",
+             report.producer_waits, report.consumer_waits);
+    narrate!("   Whichever side is faster blocks, so the queue never grows past its capacity.");
+    
+    report
+}
+
+/// Demonstrates Barrier: no worker starts phase 2 until every worker has finished phase 1
+pub fn barrier_demo(num_workers: usize) -> Vec<PhaseTimes> {
+    narrate!("🚧 Demonstrating Barrier rendezvous with {} workers...", num_workers);
+    
+    let barrier = Barrier::new(num_workers);
+    let start = Instant::now();
+    
+    let times: Vec<PhaseTimes> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..num_workers)
+            .map(|i| {
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    // Phase 1: deliberately uneven work so workers arrive at different times
+                    thread::sleep(Duration::from_millis(5 * (i as u64 + 1)));
+                    let phase1_end = start.elapsed();
+                    
+                    // Exactly one waiter is told it's the leader once everyone has arrived
+                    if barrier.wait().is_leader() {
+                        narrate!("  All {} workers reached the barrier", num_workers);
+                    }
+                    
+                    // Phase 2: everyone starts together
+                    let phase2_start = start.elapsed();
+                    PhaseTimes { phase1_end, phase2_start }
+                })
+            })
+            .collect();
+        
+        handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+    });
+    
+    for (i, t) in times.iter().enumerate() {
+        narrate!("  Worker {}: phase 1 done at {:>9.3?}, phase 2 began at {:>9.3?}",
+                 i, t.phase1_end, t.phase2_start);
+    }
+    narrate!("✅ Every phase 2 began after the slowest phase 1 finished");
+    
+    times
+}
+
+/// Outcome of the deadlock avoidance demo
+#[derive(Debug)]
+pub struct DeadlockReport {
+    pub transfers: usize,
+    pub backoffs: usize,
+    pub total: i64,
+}
+
+/// Demonstrates deadlock avoidance: two threads lock two mutexes in opposite order,
+/// but back off with try_lock instead of blocking while holding the first lock
+pub fn deadlock_timeout_demo(transfers_per_thread: usize) -> DeadlockReport {
+    narrate!("🔐 Demonstrating deadlock avoidance with try_lock and backoff...");
+    
+    let start = Instant::now();
+    let left = Mutex::new(DEADLOCK_DEMO_BALANCE);
+    let right = Mutex::new(DEADLOCK_DEMO_BALANCE);
+    let backoffs = AtomicUsize::new(0);
+    
+    // Thread 0 locks left then right, thread 1 right then left: the classic deadlock shape
+    thread::scope(|scope| {
+        for (i, (first, second)) in [(&left, &right), (&right, &left)].into_iter().enumerate() {
+            let backoffs = &backoffs;
+            scope.spawn(move || {
+                for _ in 0..transfers_per_thread {
+                    let mut attempt = 0u32;
+                    loop {
+                        let mut from = first.lock().unwrap();
+                        match second.try_lock() {
+                            Ok(mut to) => {
+                                *from -= 1;
+                                *to += 1;
+                                break;
+                            }
+                            Err(_) => {
+                                // Release the first lock so the other thread can finish,
+                                // then wait a little longer each time (staggered per thread)
+                                drop(from);
+                                backoffs.fetch_add(1, Ordering::Relaxed);
+                                thread::sleep(Duration::from_micros((i as u64 + 1) << attempt.min(6)));
+                                attempt += 1;
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    });
+    
+    let report = DeadlockReport {
+        transfers: 2 * transfers_per_thread,
+        backoffs: backoffs.into_inner(),
+        total: left.into_inner().unwrap() + right.into_inner().unwrap(),
+    };
+    
+    narrate!("✅ {} transfers completed in {:?} with {} backoffs; total still {}",
+             report.transfers, start.elapsed(), report.backoffs, report.total);
+    narrate!("📝 Note: with second.lock() instead of try_lock(), each thread can end up holding");
+    narrate!("   one mutex while blocking forever on the other. Backing off breaks the cycle;");
+    narrate!("   always locking in one global order (left before right) avoids it outright.");
+    
+    report
+}
+
+/// Demonstrates pattern matching with Option and Result types
+pub fn safe_operations_demo() {
+    narrate!("🛡️  Demonstrating safe operations with Option and Result...");
+    
+    let numbers = [10, 20, 0, 5];
+    
+    for (i, &num) in numbers.iter().enumerate() {
+        match divide_safely(100, num) {
+            Ok(result) => narrate!("100 / {} = {}", num, result),
+            Err(msg) => narrate!("Error dividing by {}: {}", num, msg),
+        }
+        
+        // Safe array access
+        match numbers.get(i + 10) {
+            Some(value) => narrate!("Value at index {}: {}", i + 10, value),
+            None => narrate!("No value at index {} (safe bounds checking)", i + 10),
+        }
+    }
+}
+
+/// Generic over any numeric type - monomorphized, so each instantiation is as fast as a hand-written one
+pub fn divide_safely<T: ToPrimitive + Zero>(a: T, b: T) -> Result<f64, DivError> {
+    if b.is_zero() {
+        return Err(DivError::DivByZero);
+    }
+    
+    match (a.to_f64(), b.to_f64()) {
+        (Some(a), Some(b)) => Ok(a / b),
+        _ => Err(DivError::NotRepresentable),
+    }
+}
+
+/// Runs every demo in sequence and gathers the results worth reporting
+pub fn run_demo(config: &DemoConfig, shutdown: &Arc<AtomicBool>) -> Result<DemoReport, Box<dyn Error>> {
+    let mut timings = BTreeMap::new();
+    let rules = &config.rules;
+    
+    narrate!("🦀 Welcome to the Rust Strengths Demonstration! 🦀");
+    narrate!("================================================");
+    
+    // 1. Memory Safety & Zero-cost Abstractions
+    let stage = Instant::now();
+    let data = match &config.load_data {
+        Some(path) => load_data(path, rules)?,
+        None => generate_sample_data(config.size),
+    };
+    if let Some(path) = &config.save_data {
+        save_data(&data, path)?;
+    }
+    timings.insert("data_generation".to_string(), elapsed_ms(stage));
+    
+    let values: Vec<f64> = data.iter().map(|point| point.value).collect();
+    if let Some(summary) = stats(&values) {
+        narrate!("📐 Value stats: mean {:.3}, variance {:.3}, min {:.3}, max {:.3}",
+                 summary.mean, summary.variance, summary.min, summary.max);
+        narrate!("   Percentiles: p50 {:.3}, p90 {:.3}, p99 {:.3}", summary.p50, summary.p90, summary.p99);
+    }
+    if let Some(bins) = config.histogram_bins {
+        print_histograms(&category_histograms(&data, bins), bins);
+    }
+    
+    // 2. Pattern Matching & Error Handling
+    safe_operations_demo();
+    narrate!();
+    
+    // 3. Ownership System
+    ownership_demo();
+    narrate!();
+    
+    // 4. Parallel Processing (Data Parallelism)
+    let stage = Instant::now();
+    let category_sums = match parallel_data_processing_on_pool(&data, rules, config.threads) {
+        Ok(results) => {
+            let sorted: BTreeMap<_, _> = results.into_iter().collect();
+            essential!("📈 Category sums:");
+            for (category, sum) in &sorted {
+                essential!("  {}: {:.2}", category, sum);
+            }
+            sorted
+        }
+        Err(e) => {
+            essential!("❌ Processing error: {}", e);
+            BTreeMap::new()
+        }
+    };
+    timings.insert("parallel_processing".to_string(), elapsed_ms(stage));
+    narrate!();
+    
+    // 5. Safe Concurrency
+    let stage = Instant::now();
+    let final_count = concurrent_counter_demo(config.threads, COUNTER_DEMO_INCREMENTS, shutdown);
+    timings.insert("concurrent_counter".to_string(), elapsed_ms(stage));
+    narrate!();
+    
+    // Ctrl-C is only noticed between stages; whatever finished so far is still reported
+    macro_rules! stop_if_interrupted {
+        () => {
+            if shutdown.load(Ordering::Relaxed) {
+                narrate!("🛑 Interrupted - skipping the remaining demos");
+                return Ok(DemoReport {
+                    data_points: data.len(),
+                    threads: config.threads,
+                    category_sums,
+                    final_count,
+                    interrupted: true,
+                    timings,
+                });
+            }
+        };
+    }
+    
+    stop_if_interrupted!();
+    assert_eq!(final_count, config.threads as u64 * COUNTER_DEMO_INCREMENTS);
+    
+    // 6. Threading Demo
+    let stage = Instant::now();
+    threading_demo()?;
+    timings.insert("threading".to_string(), elapsed_ms(stage));
+    narrate!();
+    stop_if_interrupted!();
+    
+    #[cfg(feature = "async")]
+    {
+        let stage = Instant::now();
+        tokio::runtime::Runtime::new()?.block_on(async_demo())?;
+        timings.insert("async".to_string(), elapsed_ms(stage));
+        narrate!();
+        stop_if_interrupted!();
+    }
+    
+    // 7. Message Passing
+    let stage = Instant::now();
+    channel_pipeline_demo(&data, config.threads, rules)?;
+    timings.insert("channel_pipeline".to_string(), elapsed_ms(stage));
+    narrate!();
+    stop_if_interrupted!();
+    
+    #[cfg(feature = "crossbeam")]
+    {
+        let stage = Instant::now();
+        let queue = crossbeam_pipeline_demo(2, config.threads, config.size);
+        assert_eq!(queue.consumed, queue.produced);
+        timings.insert("crossbeam_pipeline".to_string(), elapsed_ms(stage));
+        narrate!();
+        stop_if_interrupted!();
+    }
+    
+    // 8. Reader/Writer Locks
+    let stage = Instant::now();
+    rwlock_demo(&data, config.threads * 2, 2);
+    timings.insert("rwlock".to_string(), elapsed_ms(stage));
+    narrate!();
+    stop_if_interrupted!();
+    
+    // 9. Scoped Threads
+    let stage = Instant::now();
+    scoped_threads_demo(&data, config.threads);
+    timings.insert("scoped_threads".to_string(), elapsed_ms(stage));
+    narrate!();
+    stop_if_interrupted!();
+    
+    // 10. Load Balancing
+    let stage = Instant::now();
+    load_balancing_demo(&data, config.threads)?;
+    timings.insert("load_balancing".to_string(), elapsed_ms(stage));
+    narrate!();
+    stop_if_interrupted!();
+    
+    // 11. Condition Variables
+    let stage = Instant::now();
+    let queue = condvar_demo(2, config.threads, config.size, 16);
+    assert_eq!(queue.consumed, queue.produced);
+    timings.insert("condvar".to_string(), elapsed_ms(stage));
+    narrate!();
+    stop_if_interrupted!();
+    
+    // 12. Barrier Synchronization
+    let stage = Instant::now();
+    barrier_demo(config.threads.max(1));
+    timings.insert("barrier".to_string(), elapsed_ms(stage));
+    narrate!();
+    stop_if_interrupted!();
+    
+    // 13. Deadlock Avoidance
+    let stage = Instant::now();
+    let deadlock = deadlock_timeout_demo(DEADLOCK_DEMO_TRANSFERS);
+    assert_eq!(deadlock.total, 2 * DEADLOCK_DEMO_BALANCE);
+    timings.insert("deadlock_avoidance".to_string(), elapsed_ms(stage));
+    narrate!();
+    stop_if_interrupted!();
+    
+    // 14. Panic Recovery
+    let stage = Instant::now();
+    let workers = config.threads.max(2);
+    let recovery = panic_recovery_demo(workers, 1);
+    let healthy = (workers - recovery.panicked_workers.len()) as u64;
+    assert_eq!(recovery.final_count, healthy * PANIC_DEMO_INCREMENTS);
+    timings.insert("panic_recovery".to_string(), elapsed_ms(stage));
+    narrate!();
+    
+    Ok(DemoReport {
+        data_points: data.len(),
+        threads: config.threads,
+        category_sums,
+        final_count,
+        interrupted: false,
+        timings,
+    })
+}
+
+fn elapsed_ms(start: Instant) -> f64 {
+    start.elapsed().as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_data_generation() {
+        let data = generate_sample_data(10);
+        assert_eq!(data.len(), 10);
+        assert!(data.iter().all(|point| !point.category.is_empty()));
+    }
+    
+    #[test]
+    fn test_safe_division() {
+        assert!(divide_safely(10, 2).is_ok());
+        assert!(divide_safely(10, 0).is_err());
+    }
+    
+    #[test]
+    fn test_safe_division_generic() {
+        assert_eq!(divide_safely(9u8, 2u8), Ok(4.5));
+        assert_eq!(divide_safely(-10_000_000_000i64, 4i64), Ok(-2_500_000_000.0));
+        assert_eq!(divide_safely(1.5f32, 0.0f32), Err(DivError::DivByZero));
+        assert_eq!(divide_safely(7u8, 0u8), Err(DivError::DivByZero));
+    }
+    
+    #[test]
+    fn test_stats_known_slice() {
+        let summary = stats(&[2u8, 4, 4, 4, 5, 5, 7, 9]).unwrap();
+        assert_eq!(summary.count, 8);
+        assert_eq!(summary.mean, 5.0);
+        assert_eq!(summary.variance, 4.0);
+        assert_eq!((summary.min, summary.max), (2.0, 9.0));
+        assert_eq!(summary.p50, 4.5);
+        
+        assert_eq!(stats(&[1.5f32]).unwrap().p99, 1.5);
+        assert!(stats::<f64>(&[]).is_none());
+    }
+    
+    #[test]
+    fn test_data_validation() {
+        let rules = ValidationRules::default();
+        let valid_point = DataPoint {
+            id: 1,
+            value: 42.0,
+            category: "Alpha".to_string(),
+            timestamp: 12345,
+        };
+        assert!(validate_data_point(&valid_point, &rules).is_ok());
+        
+        let invalid_point = DataPoint {
+            id: 2,
+            value: f64::NAN,
+            category: "Alpha".to_string(),
+            timestamp: 12345,
+        };
+        assert!(validate_data_point(&invalid_point, &rules).is_err());
+    }
+    
+    #[test]
+    fn test_validation_rules() {
+        let rules = ValidationRules {
+            min_value: -10.0,
+            max_value: 10.0,
+            allowed_categories: vec!["Alpha".to_string()],
+        };
+        let out_of_range = DataPoint {
+            id: 3,
+            value: 42.5,
+            category: "Alpha".to_string(),
+            timestamp: 12345,
+        };
+        let message = validate_data_point(&out_of_range, &rules).unwrap_err().to_string();
+        assert!(message.contains("value 42.5") && message.contains("ID 3"));
+        
+        let unknown_category = DataPoint {
+            id: 4,
+            value: 1.0,
+            category: "Omega".to_string(),
+            timestamp: 12345,
+        };
+        let message = validate_data_point(&unknown_category, &rules).unwrap_err().to_string();
+        assert!(message.contains("category 'Omega'") && message.contains("ID 4"));
+        
+        // Tightened rules surface through the parallel pipeline as well
+        assert!(parallel_data_processing(&generate_sample_data(100), &rules).is_err());
+    }
+    
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let data = generate_sample_data(50);
+        let rules = ValidationRules::default();
+        
+        for extension in ["json", "csv"] {
+            let path = std::env::temp_dir()
+                .join(format!("rust-demo-roundtrip-{}.{}", std::process::id(), extension));
+            let path = path.to_str().unwrap();
+            
+            save_data(&data, path).unwrap();
+            let loaded = load_data(path, &rules).unwrap();
+            fs::remove_file(path).unwrap();
+            
+            assert_eq!(loaded, data, "{} round trip", extension);
+        }
+        
+        assert!(save_data(&data, "dataset.txt").is_err());
+    }
+    
+    #[test]
+    fn test_category_sums_csv() {
+        let data = generate_sample_data(100);
+        let sums: BTreeMap<_, _> = parallel_data_processing(&data, &ValidationRules::default())
+            .unwrap()
+            .into_iter()
+            .collect();
+        
+        let path = std::env::temp_dir().join(format!("rust-demo-sums-{}.csv", std::process::id()));
+        let path = path.to_str().unwrap();
+        save_category_sums(&sums, path).unwrap();
+        
+        let mut reader = csv::Reader::from_path(path).unwrap();
+        assert_eq!(reader.headers().unwrap(), vec!["category", "sum"]);
+        let rows: Vec<(String, f64)> = reader.deserialize().collect::<Result<_, _>>().unwrap();
+        fs::remove_file(path).unwrap();
+        
+        assert_eq!(rows, sums.into_iter().collect::<Vec<_>>());
+        assert_eq!(rows.iter().map(|(category, _)| category.as_str()).collect::<Vec<_>>(),
+                   vec!["Alpha", "Beta", "Delta", "Gamma"]);
+    }
+    
+    #[test]
+    fn test_histogram_counts_every_point() {
+        let data = generate_sample_data(1000);
+        let histograms = category_histograms(&data, 7);
+        
+        assert_eq!(histograms.len(), 4);
+        assert!(histograms.values().all(|histogram| histogram.counts.len() == 7));
+        let total: usize = histograms.values().flat_map(|histogram| &histogram.counts).sum();
+        assert_eq!(total, data.len());
+    }
+    
+    #[test]
+    fn test_pool_size_does_not_change_sums() {
+        let data = generate_sample_data(1000);
+        let rules = ValidationRules::default();
+        
+        let single = parallel_data_processing_on_pool(&data, &rules, 1).unwrap();
+        let multi = parallel_data_processing_on_pool(&data, &rules, 4).unwrap();
+        assert_eq!(single, multi);
+    }
+    
+    #[test]
+    fn test_load_balancing_strategies_agree() {
+        let data = generate_sample_data(1000);
+        let stealing = work_stealing_sums(&data);
+        let chunked = static_chunking_sums(&data, 3);
+        
+        // Same categories and totals; only the float summation order differs
+        assert_eq!(stealing.keys().collect::<Vec<_>>(), chunked.keys().collect::<Vec<_>>());
+        for (category, sum) in &stealing {
+            assert!((sum - chunked[category]).abs() < 1e-9, "{}: {} vs {}", category, sum, chunked[category]);
+        }
+        
+        let expected = parallel_data_processing(&data, &ValidationRules::default()).unwrap();
+        assert!((stealing["Delta"] - expected["Delta"]).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn test_parallel_processing_emits_span() {
+        use tracing::span::{Attributes, Id};
+        use tracing::Subscriber;
+        use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+        
+        /// Records the name of every span that is opened
+        struct SpanNames(Arc<Mutex<Vec<String>>>);
+        
+        impl<S: Subscriber> Layer<S> for SpanNames {
+            fn on_new_span(&self, attrs: &Attributes<'_>, _id: &Id, _ctx: Context<'_, S>) {
+                self.0.lock().unwrap().push(attrs.metadata().name().to_string());
+            }
+        }
+        
+        let names = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(SpanNames(Arc::clone(&names)));
+        tracing::subscriber::with_default(subscriber, || {
+            parallel_data_processing(&generate_sample_data(10), &ValidationRules::default()).unwrap();
+        });
+        
+        assert!(names.lock().unwrap().iter().any(|name| name == "parallel_processing"));
+    }
+    
+    #[test]
+    fn test_processing_error_display() {
+        let point = DataPoint {
+            id: 7,
+            value: f64::INFINITY,
+            category: "Test".to_string(),
+            timestamp: 12345,
+        };
+        let error = validate_data_point(&point, &ValidationRules::default()).unwrap_err();
+        assert!(error.to_string().contains("ID 7"));
+        assert!(error.source().is_none());
+        
+        let io_error = ProcessingError::from(std::io::Error::other("disk full"));
+        assert!(io_error.to_string().contains("disk full"));
+        assert!(io_error.source().is_some());
+    }
+    
+    #[test]
+    fn test_json_report_round_trip() {
+        let config = DemoConfig { threads: 2, size: 20, ..DemoConfig::default() };
+        let report = run_demo(&config, &Arc::new(AtomicBool::new(false))).unwrap();
+        
+        let json = serde_json::to_string(&report).unwrap();
+        let parsed: DemoReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.final_count, 200_000);
+        assert_eq!(parsed.data_points, 20);
+        assert_eq!(parsed.category_sums.len(), 4);
+        assert!(parsed.timings.contains_key("parallel_processing"));
+    }
+    
+    #[test]
+    fn test_condvar_conserves_items() {
+        let report = condvar_demo(3, 2, 500, 2);
+        assert_eq!(report.produced, 1500);
+        assert_eq!(report.consumed, report.produced);
+        
+        // Zero consumers is clamped to one rather than leaving the producers blocked
+        assert_eq!(condvar_demo(1, 0, 50, 1).consumed, 50);
+    }
+    
+    #[cfg(feature = "crossbeam")]
+    #[test]
+    fn test_crossbeam_pipeline_conserves_messages() {
+        let report = crossbeam_pipeline_demo(3, 4, 500);
+        assert_eq!(report.produced, 1500);
+        assert_eq!(report.consumed, report.produced);
+        assert_eq!(report.per_consumer.len(), 4);
+    }
+    
+    #[test]
+    fn test_barrier_rendezvous() {
+        let times = barrier_demo(4);
+        assert_eq!(times.len(), 4);
+        
+        let last_phase1_end = times.iter().map(|t| t.phase1_end).max().unwrap();
+        assert!(times.iter().all(|t| t.phase2_start >= last_phase1_end));
+    }
+    
+    #[test]
+    fn test_deadlock_demo_terminates() {
+        // Watchdog: a hung demo fails the test instead of hanging the whole run
+        let (done_tx, done_rx) = mpsc::channel();
+        thread::spawn(move || done_tx.send(deadlock_timeout_demo(200)).unwrap());
+        
+        let report = done_rx.recv_timeout(Duration::from_secs(10)).expect("deadlock demo hung");
+        assert_eq!(report.transfers, 400);
+        assert_eq!(report.total, 2 * DEADLOCK_DEMO_BALANCE);
+    }
+    
+    #[test]
+    fn test_panic_recovery() {
+        let report = panic_recovery_demo(4, 2);
+        assert_eq!(report.panicked_workers, vec![2]);
+        assert_eq!(report.final_count, 3 * PANIC_DEMO_INCREMENTS);
+    }
+    
+    #[test]
+    fn test_counter_stops_on_shutdown() {
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let trigger = Arc::clone(&shutdown);
+        let watcher = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            trigger.store(true, Ordering::Relaxed);
+        });
+        
+        // Far more work than could finish before the flag is set
+        let final_count = concurrent_counter_demo(4, u64::MAX / 8, &shutdown);
+        watcher.join().unwrap();
+        assert!(final_count < 4 * (u64::MAX / 8));
+        
+        let finished = concurrent_counter_demo(2, 1000, &Arc::new(AtomicBool::new(false)));
+        assert_eq!(finished, 2000);
+    }
+    
+    #[test]
+    fn test_interrupted_demo_skips_later_stages() {
+        let config = DemoConfig { threads: 2, size: 50, ..DemoConfig::default() };
+        let report = run_demo(&config, &Arc::new(AtomicBool::new(true))).unwrap();
+        
+        assert!(report.interrupted);
+        assert_eq!(report.data_points, 50);
+        assert!(report.timings.contains_key("concurrent_counter"));
+        assert!(!report.timings.contains_key("threading"));
+    }
+    
+    #[test]
+    fn test_bench_counters_agree() {
+        let results = bench_counters(4, 1000);
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|result| result.final_count == 4000));
+        assert!(results.iter().all(|result| result.nanos_per_increment > 0.0));
+    }
+    
+    #[cfg(feature = "async")]
+    #[tokio::test]
+    async fn test_async_tasks_complete() {
+        let results = async_demo().await.unwrap();
+        assert_eq!(results.len(), 3);
+        assert!(results.iter().all(|result| result.ends_with("completed")));
+    }
+    
+    #[test]
+    fn test_channel_pipeline_delivers_all_items() {
+        let collected = channel_pipeline_demo(&generate_sample_data(1000), 3, &ValidationRules::default()).unwrap();
+        assert_eq!(collected.len(), 1000);
+        
+        let mut ids: Vec<u32> = collected.iter().map(|point| point.id).collect();
+        ids.sort_unstable();
+        assert!(ids.into_iter().eq(0..1000));
+    }
+    
+    #[test]
+    fn test_rwlock_concurrent_reads() {
+        // A second reader gets in while the first guard is still held
+        let lock = Arc::new(RwLock::new(generate_sample_data(10)));
+        let held = lock.read().unwrap();
+        let other = Arc::clone(&lock);
+        let len = thread::spawn(move || other.read().unwrap().len()).join().unwrap();
+        assert_eq!(len, held.len());
+        drop(held);
+        
+        let data = generate_sample_data(100);
+        let final_len = rwlock_demo(&data, 8, 2);
+        assert_eq!(final_len, 100 + 2 * WRITES_PER_WRITER);
+    }
+    
+    #[test]
+    fn test_scoped_threads_match_sequential_sum() {
+        let data = generate_sample_data(1001);
+        let chunk_sums = scoped_threads_demo(&data, 4);
+        assert_eq!(chunk_sums.len(), 4);
+        
+        let sequential: f64 = data.iter().map(|point| point.value).sum();
+        let scoped: f64 = chunk_sums.iter().sum();
+        assert!((sequential - scoped).abs() < 1e-6);
+    }
+}
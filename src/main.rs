@@ -1,19 +1,22 @@
-use clap::Parser;
-use rayon::prelude::*;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use clap::{Parser, Subcommand, ValueEnum};
+use rust_strengths_demo::{
+    bench_counters, narrate, run_demo, save_category_sums, set_narration, DemoConfig, Narration,
+    ValidationRules, KNOWN_CATEGORIES,
+};
+use std::error::Error;
 use std::fs;
-use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::{Duration, Instant};
-
-mod educational_examples;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tracing_subscriber::EnvFilter;
 
 /// A program demonstrating Rust's key strengths
 #[derive(Parser)]
 #[command(name = "rust-demo")]
 #[command(about = "Demonstrates Rust's strengths: safety, performance, and concurrency")]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+    
     /// Number of worker threads to spawn
     #[arg(short, long, default_value_t = 4)]
     threads: usize,
@@ -21,282 +24,195 @@ struct Args {
     /// Size of data to process (in thousands)
     #[arg(short, long, default_value_t = 1000)]
     size: usize,
-}
-
-/// Represents a data point in our processing pipeline
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct DataPoint {
-    id: u32,
-    value: f64,
-    category: String,
-    timestamp: u64,
-}
-
-/// Custom error type demonstrating Rust's error handling
-#[derive(Debug)]
-enum ProcessingError {
-    InvalidData(String),
-    ComputationError(String),
-    IoError(std::io::Error),
-}
-
-impl From<std::io::Error> for ProcessingError {
-    fn from(error: std::io::Error) -> Self {
-        ProcessingError::IoError(error)
-    }
-}
-
-/// Result type alias for cleaner code
-type ProcessingResult<T> = Result<T, ProcessingError>;
-
-/// Demonstrates memory safety and zero-cost abstractions
-fn generate_sample_data(size: usize) -> Vec<DataPoint> {
-    println!("🔧 Generating {} data points...", size);
     
-    // Iterator chains compile to highly optimized loops
-    (0..size)
-        .map(|i| DataPoint {
-            id: i as u32,
-            value: (i as f64 * 3.14159).sin() * 100.0,
-            category: match i % 4 {
-                0 => "Alpha".to_string(),
-                1 => "Beta".to_string(),
-                2 => "Gamma".to_string(),
-                _ => "Delta".to_string(),
-            },
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs() + i as u64,
-        })
-        .collect()
-}
-
-/// Demonstrates pattern matching and error handling
-fn validate_data_point(point: &DataPoint) -> ProcessingResult<()> {
-    match point {
-        DataPoint { value, .. } if value.is_nan() || value.is_infinite() => {
-            Err(ProcessingError::InvalidData(format!("Invalid value for ID {}", point.id)))
-        }
-        DataPoint { category, .. } if category.is_empty() => {
-            Err(ProcessingError::InvalidData(format!("Empty category for ID {}", point.id)))
-        }
-        _ => Ok(()),
-    }
-}
-
-/// Demonstrates parallel processing with Rayon (data parallelism)
-fn parallel_data_processing(data: &[DataPoint]) -> ProcessingResult<HashMap<String, f64>> {
-    println!("⚡ Processing data in parallel using Rayon...");
+    /// Smallest accepted data point value
+    #[arg(long, default_value_t = -100.0, allow_negative_numbers = true)]
+    min_value: f64,
     
-    let start = Instant::now();
+    /// Largest accepted data point value
+    #[arg(long, default_value_t = 100.0, allow_negative_numbers = true)]
+    max_value: f64,
     
-    // Parallel iterator - automatically distributes work across CPU cores
-    let validated_data: ProcessingResult<Vec<_>> = data
-        .par_iter()  // Convert to parallel iterator
-        .map(|point| {
-            // Validate each point
-            validate_data_point(point)?;
-            Ok((point.category.clone(), point.value))
-        })
-        .collect();
+    /// Accepted categories, comma-separated
+    #[arg(long, value_delimiter = ',', default_values_t = KNOWN_CATEGORIES.map(String::from))]
+    categories: Vec<String>,
     
-    let result = validated_data?
-        .into_iter()
-        .fold(HashMap::new(), |mut acc: HashMap<String, f64>, (category, value)| {
-            *acc.entry(category).or_insert(0.0) += value;
-            acc
-        });
+    /// Save the dataset to this path (.json or .csv)
+    #[arg(long)]
+    save_data: Option<String>,
     
-    let duration = start.elapsed();
-    println!("✅ Parallel processing completed in {:?}", duration);
+    /// Replay a dataset saved with --save-data instead of generating one
+    #[arg(long)]
+    load_data: Option<String>,
     
-    Ok(result)
-}
-
-/// Demonstrates safe concurrency with shared state
-fn concurrent_counter_demo(num_threads: usize) -> u64 {
-    println!("🔄 Demonstrating safe concurrent access with {} threads...", num_threads);
+    /// Also write the category sums to this path as a category,sum CSV
+    #[arg(long)]
+    sums_csv: Option<String>,
     
-    // Arc (Atomically Reference Counted) allows sharing between threads
-    // Mutex ensures safe access to shared data
-    let counter = Arc::new(Mutex::new(0u64));
-    let mut handles = Vec::new();
+    /// Print a per-category histogram of values with this many buckets
+    #[arg(long, value_name = "BINS")]
+    histogram: Option<usize>,
     
-    let start = Instant::now();
+    /// Report format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
     
-    for i in 0..num_threads {
-        let counter_clone = Arc::clone(&counter);
-        
-        let handle = thread::spawn(move || {
-            // Each thread does some work
-            for _ in 0..100_000 {
-                // Lock is automatically released when it goes out of scope
-                let mut num = counter_clone.lock().unwrap();
-                *num += 1;
-                // Lock is dropped here - RAII (Resource Acquisition Is Initialization)
-            }
-            
-            println!("Thread {} completed", i);
-        });
-        
-        handles.push(handle);
-    }
+    /// Where to write the report (text: demo_results.txt, json: stdout)
+    #[arg(short, long)]
+    output: Option<String>,
     
-    // Wait for all threads to complete
-    for handle in handles {
-        handle.join().unwrap();
-    }
+    /// Print the decorated emoji narration instead of log events
+    #[arg(long)]
+    pretty: bool,
     
-    let final_count = *counter.lock().unwrap();
-    let duration = start.elapsed();
+    /// Print only the essential results: category sums and the final count
+    #[arg(short, long)]
+    quiet: bool,
     
-    println!("✅ Concurrent counting completed in {:?}", duration);
-    println!("📊 Final count: {} (expected: {})", final_count, num_threads * 100_000);
+    /// Log filter such as "info" or "rust_strengths_demo=debug" (overrides RUST_LOG)
+    #[arg(long)]
+    log_level: Option<String>,
     
-    final_count
+    /// Emit log events as JSON lines
+    #[arg(long)]
+    log_json: bool,
 }
 
-/// Demonstrates ownership and borrowing
-fn ownership_demo() {
-    println!("🏠 Demonstrating Rust's ownership system...");
-    
-    // String creation - heap allocated
-    let mut original = String::from("Hello, Rust!");
-    println!("Original: {}", original);
-    
-    // Borrowing (immutable reference) - no ownership transfer
-    let borrowed = &original;
-    println!("Borrowed: {}", borrowed);
-    
-    // Mutable borrowing
-    let mutable_borrow = &mut original;
-    mutable_borrow.push_str(" 🦀");
-    println!("After mutation: {}", mutable_borrow);
-    
-    // Move semantics - ownership transferred
-    let moved = original;  // original is no longer accessible
-    println!("Moved: {}", moved);
-    
-    // This would cause a compile error:
-    // println!("Original after move: {}", original);
-    
-    println!("✅ No memory leaks, no dangling pointers, all memory safely managed!");
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
 }
 
-/// Demonstrates async/await (bonus feature)
-fn threading_demo() -> ProcessingResult<()> {
-    println!("🚀 Demonstrating thread spawning...");
-    
-    let handles: Vec<_> = (0..3)
-        .map(|i| {
-            thread::spawn(move || {
-                thread::sleep(Duration::from_millis(100 + i * 50));
-                format!("Task {} completed", i + 1)
-            })
-        })
-        .collect();
-    
-    // Wait for all tasks
-    for handle in handles {
-        let result = handle.join().unwrap();
-        println!("📡 {}", result);
-    }
-    
-    println!("✅ All threaded tasks completed!");
-    Ok(())
+/// Focused measurements; running without a subcommand performs the full demo
+#[derive(Subcommand)]
+enum Command {
+    /// Time Mutex, AtomicU64, and RwLock counters on identical workloads
+    BenchCounter {
+        /// Number of threads incrementing concurrently
+        #[arg(short, long, default_value_t = 4)]
+        threads: usize,
+        
+        /// Increments performed by each thread
+        #[arg(short, long, default_value_t = 100_000)]
+        increments: usize,
+    },
 }
 
-/// Demonstrates pattern matching with Option and Result types
-fn safe_operations_demo() {
-    println!("🛡️  Demonstrating safe operations with Option and Result...");
-    
-    let numbers = vec![10, 20, 0, 5];
-    
-    for (i, &num) in numbers.iter().enumerate() {
-        match divide_safely(100, num) {
-            Ok(result) => println!("100 / {} = {}", num, result),
-            Err(msg) => println!("Error dividing by {}: {}", num, msg),
-        }
-        
-        // Safe array access
-        match numbers.get(i + 10) {
-            Some(value) => println!("Value at index {}: {}", i + 10, value),
-            None => println!("No value at index {} (safe bounds checking)", i + 10),
+impl Args {
+    fn demo_config(&self) -> DemoConfig {
+        DemoConfig {
+            threads: self.threads,
+            size: self.size,
+            rules: ValidationRules {
+                min_value: self.min_value,
+                max_value: self.max_value,
+                allowed_categories: self.categories.clone(),
+            },
+            load_data: self.load_data.clone(),
+            save_data: self.save_data.clone(),
+            histogram_bins: self.histogram,
         }
     }
 }
 
-fn divide_safely(a: i32, b: i32) -> Result<f64, String> {
-    if b == 0 {
-        Err("Division by zero".to_string())
+/// Sends tracing output to stderr, filtered by --log-level, then RUST_LOG, then "info"
+fn init_logging(args: &Args) -> Result<(), Box<dyn Error>> {
+    let filter = match &args.log_level {
+        Some(level) => EnvFilter::try_new(level)?,
+        None => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+    };
+    let logs = tracing_subscriber::fmt().with_env_filter(filter).with_writer(std::io::stderr);
+    
+    if args.log_json {
+        logs.json().init();
     } else {
-        Ok(a as f64 / b as f64)
+        logs.init();
     }
+    
+    Ok(())
 }
 
-fn main() -> ProcessingResult<()> {
+fn main() -> Result<(), Box<dyn Error>> {
     let args = Args::parse();
     
-    println!("🦀 Welcome to the Rust Strengths Demonstration! 🦀");
-    println!("================================================");
-    
-    // 1. Memory Safety & Zero-cost Abstractions
-    let data = generate_sample_data(args.size);
-    
-    // 2. Pattern Matching & Error Handling
-    safe_operations_demo();
-    println!();
-    
-    // 3. Ownership System
-    ownership_demo();
-    println!();
-    
-    // 4. Parallel Processing (Data Parallelism)
-    match parallel_data_processing(&data) {
-        Ok(results) => {
-            println!("📈 Category sums:");
-            for (category, sum) in results {
-                println!("  {}: {:.2}", category, sum);
-            }
+    match args.command {
+        Some(Command::BenchCounter { threads, increments }) => {
+            set_narration(Narration::Pretty);
+            bench_counters(threads, increments);
+            return Ok(());
         }
-        Err(e) => println!("❌ Processing error: {:?}", e),
+        None => {}
     }
-    println!();
     
-    // 5. Safe Concurrency
-    let final_count = concurrent_counter_demo(args.threads);
-    assert_eq!(final_count, args.threads as u64 * 100_000);
-    println!();
+    init_logging(&args)?;
+    
+    // Log events go to stderr; printed narration shares stdout, so it can't accompany JSON there
+    let json_on_stdout = args.format == OutputFormat::Json && args.output.is_none();
+    let printed = args.pretty || args.quiet;
+    set_narration(match (printed, json_on_stdout) {
+        (true, true) => Narration::Off,
+        (true, false) if args.quiet => Narration::Quiet,
+        (true, false) => Narration::Pretty,
+        (false, _) => Narration::Log,
+    });
+    
+    // Ctrl-C flips the flag; the current stage finishes, the rest are skipped, and we still
+    // print a report. A second Ctrl-C doesn't wait for the stage and exits at once
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let handler_flag = Arc::clone(&shutdown);
+    ctrlc::set_handler(move || {
+        if handler_flag.swap(true, Ordering::Relaxed) {
+            std::process::exit(130);
+        }
+    })?;
     
-    // 6. Threading Demo
-    threading_demo()?;
-    println!();
+    let report = run_demo(&args.demo_config(), &shutdown)?;
     
-    // Save results to file (demonstrating error handling)
-    let summary = format!(
-        "Rust Demo Summary:\n\
-         - Processed {} data points\n\
-         - Used {} threads for concurrent operations\n\
-         - All operations completed safely without memory leaks\n\
-         - Zero-cost abstractions provided high performance\n\
-         - Pattern matching enabled robust error handling\n",
-        args.size, args.threads
-    );
+    if let Some(path) = &args.sums_csv {
+        save_category_sums(&report.category_sums, path)?;
+    }
     
-    match fs::write("demo_results.txt", &summary) {
-        Ok(_) => println!("📄 Results saved to demo_results.txt"),
-        Err(e) => println!("❌ Failed to save results: {}", e),
+    match args.format {
+        OutputFormat::Text => {
+            // Save results to file (demonstrating error handling)
+            let path = args.output.as_deref().unwrap_or("demo_results.txt");
+            let summary = format!(
+                "Rust Demo Summary:\n\
+                 - Processed {} data points\n\
+                 - Used {} threads for concurrent operations\n\
+                 - All operations completed safely without memory leaks\n\
+                 - Zero-cost abstractions provided high performance\n\
+                 - Pattern matching enabled robust error handling\n",
+                report.data_points, args.threads
+            );
+            
+            match fs::write(path, &summary) {
+                Ok(_) => narrate!("📄 Results saved to {}", path),
+                Err(e) => narrate!("❌ Failed to save results: {}", e),
+            }
+        }
+        OutputFormat::Json => {
+            let json = serde_json::to_string_pretty(&report)?;
+            match &args.output {
+                Some(path) => {
+                    fs::write(path, json)?;
+                    narrate!("📄 Report saved to {}", path);
+                }
+                None => println!("{}", json),
+            }
+        }
     }
     
-    println!("\n🎉 Rust demonstration completed successfully!");
-    println!("Key strengths demonstrated:");
-    println!("  ✅ Memory safety without garbage collection");
-    println!("  ✅ Zero-cost abstractions");
-    println!("  ✅ Fearless concurrency");
-    println!("  ✅ Pattern matching and error handling");
-    println!("  ✅ Ownership system preventing data races");
-    println!("  ✅ High performance parallel processing");
+    narrate!("\n🎉 Rust demonstration completed successfully!");
+    narrate!("Key strengths demonstrated:");
+    narrate!("  ✅ Memory safety without garbage collection");
+    narrate!("  ✅ Zero-cost abstractions");
+    narrate!("  ✅ Fearless concurrency");
+    narrate!("  ✅ Pattern matching and error handling");
+    narrate!("  ✅ Ownership system preventing data races");
+    narrate!("  ✅ Message passing between threads");
+    narrate!("  ✅ High performance parallel processing");
     
     Ok(())
 }
@@ -306,34 +222,15 @@ mod tests {
     use super::*;
     
     #[test]
-    fn test_data_generation() {
-        let data = generate_sample_data(10);
-        assert_eq!(data.len(), 10);
-        assert!(data.iter().all(|point| !point.category.is_empty()));
-    }
-    
-    #[test]
-    fn test_safe_division() {
-        assert!(divide_safely(10, 2).is_ok());
-        assert!(divide_safely(10, 0).is_err());
-    }
-    
-    #[test]
-    fn test_data_validation() {
-        let valid_point = DataPoint {
-            id: 1,
-            value: 42.0,
-            category: "Test".to_string(),
-            timestamp: 12345,
-        };
-        assert!(validate_data_point(&valid_point).is_ok());
+    fn test_args_map_onto_demo_config() {
+        let args = Args::parse_from([
+            "rust-demo", "--threads", "2", "--size", "20", "--min-value", "-5", "--categories", "Alpha,Beta",
+        ]);
+        let config = args.demo_config();
         
-        let invalid_point = DataPoint {
-            id: 2,
-            value: f64::NAN,
-            category: "Test".to_string(),
-            timestamp: 12345,
-        };
-        assert!(validate_data_point(&invalid_point).is_err());
+        assert_eq!((config.threads, config.size), (2, 20));
+        assert_eq!(config.rules.min_value, -5.0);
+        assert_eq!(config.rules.allowed_categories, vec!["Alpha", "Beta"]);
+        assert!(config.load_data.is_none());
     }
 }
@@ -0,0 +1,28 @@
+// Runs the built binary end to end and inspects what it prints
+
+use std::process::Command;
+
+/// Runs the demo binary with a small workload and returns its stdout
+fn run_demo_binary(extra_args: &[&str]) -> String {
+    let report = std::env::temp_dir().join(format!("rust-demo-cli-{}.txt", extra_args.join("")));
+    let output = Command::new(env!("CARGO_BIN_EXE_rust-strengths-demo"))
+        .args(["--threads", "2", "--size", "2", "--output"])
+        .arg(&report)
+        .args(extra_args)
+        .output()
+        .expect("demo binary should run");
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let _ = std::fs::remove_file(report);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn quiet_prints_only_essential_results() {
+    let pretty = run_demo_binary(&["--pretty"]);
+    let quiet = run_demo_binary(&["--quiet"]);
+    
+    assert!(quiet.lines().count() * 5 < pretty.lines().count());
+    assert!(quiet.contains("Final count: 200000"));
+    assert!(quiet.contains("Category sums"));
+    assert!(!quiet.contains("completed"));
+}
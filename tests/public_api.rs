@@ -0,0 +1,44 @@
+// Exercises the library the way another crate would: only through its public API
+
+use rust_strengths_demo::{
+    bench_counters, concurrent_counter_demo, generate_sample_data, parallel_data_processing,
+    run_demo, set_narration, DemoConfig, Narration, ValidationRules,
+};
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+#[test]
+fn parallel_sums_cover_every_category() {
+    set_narration(Narration::Off);
+    let data = generate_sample_data(400);
+    let sums = parallel_data_processing(&data, &ValidationRules::default()).unwrap();
+    
+    assert_eq!(sums.len(), 4);
+    let total: f64 = data.iter().map(|point| point.value).sum();
+    assert!((sums.values().sum::<f64>() - total).abs() < 1e-9);
+}
+
+#[test]
+fn counter_demo_reaches_expected_total() {
+    set_narration(Narration::Off);
+    let shutdown = Arc::new(AtomicBool::new(false));
+    assert_eq!(concurrent_counter_demo(3, 500, &shutdown), 1500);
+}
+
+#[test]
+fn bench_counter_flavours_agree() {
+    set_narration(Narration::Off);
+    let results = bench_counters(2, 250);
+    assert!(results.iter().all(|result| result.final_count == 500));
+}
+
+#[test]
+fn full_demo_reports_its_work() {
+    set_narration(Narration::Off);
+    let config = DemoConfig { threads: 2, size: 40, ..DemoConfig::default() };
+    let report = run_demo(&config, &Arc::new(AtomicBool::new(false))).unwrap();
+    
+    assert_eq!(report.data_points, 40);
+    assert_eq!(report.final_count, 2 * rust_strengths_demo::COUNTER_DEMO_INCREMENTS);
+    assert!(!report.interrupted);
+}